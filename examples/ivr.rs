@@ -0,0 +1,38 @@
+use freeswitch_esl::{Esl, EslConnection, EslError, HangupCause, IvrAction, IvrMenu, IvrOutcome};
+use tokio::net::TcpListener;
+
+async fn process_call(conn: EslConnection) -> Result<(), EslError> {
+    conn.answer().await?;
+    let support = IvrMenu::new("support", "ivr/ivr-please_hold.wav")
+        .action("1", IvrAction::Transfer("1001 XML default".into()))
+        .action("*", IvrAction::Back);
+    let main = IvrMenu::new("main", "ivr/ivr-welcome.wav")
+        .invalid_prompt("ivr/ivr-that_was_an_invalid_entry.wav")
+        .exit_prompt("voicemail/vm-goodbye.wav")
+        .action("1", IvrAction::Transfer("1000 XML default".into()))
+        .action("2", IvrAction::SubMenu(support))
+        .action("9", IvrAction::Hangup(HangupCause::NormalClearing));
+    match conn.run_ivr(&main).await? {
+        IvrOutcome::MaxFailures(menu) => {
+            println!("caller failed to select option in {}", menu);
+            conn.hangup("NORMAL_CLEARING").await?;
+        }
+        outcome => println!("ivr finished with {:?}", outcome),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), EslError> {
+    let addr = "0.0.0.0:8085"; // Listening address
+    println!("Listening on {}", addr);
+    let listener = TcpListener::bind(addr).await?;
+
+    loop {
+        let (socket, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            let stream = Esl::outbound(socket).await.unwrap();
+            process_call(stream).await.unwrap();
+        });
+    }
+}
@@ -1,21 +1,20 @@
-use freeswitch_esl::{Esl, EslConnection, EslError};
+use std::time::Duration;
+
+use freeswitch_esl::{Esl, EslConnection, EslError, PlayAndGetDigits};
 use tokio::net::TcpListener;
 
 async fn process_call(conn: EslConnection) -> Result<(), EslError> {
     conn.answer().await?;
     println!("answered call");
     conn.playback("ivr/ivr-welcome.wav").await?;
-    let digit = conn
+    let digits = conn
         .play_and_get_digits(
-            1,
-            1,
-            3,
-            3000,
-            "#",
-            "conference/conf-pin.wav",
-            "conference/conf-bad-pin.wav",
+            &PlayAndGetDigits::new("conference/conf-pin.wav")
+                .invalid_file("conference/conf-bad-pin.wav")
+                .timeout(Duration::from_secs(3)),
         )
         .await?;
+    let digit = digits.digits().ok_or(EslError::NoInput)?;
     println!("got digit {}", digit);
     conn.playback("ivr/ivr-you_entered.wav").await?;
     conn.playback(&format!("digits/{}.wav", digit)).await?;
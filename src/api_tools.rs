@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::channel::{setvar_multi_args, validate_variable_name};
+use crate::connection::whole_seconds;
+use crate::event::parse_json_body;
+use crate::transfer::Transfer;
+use crate::{EslConnection, EslError, HangupCause};
+
+impl EslConnection {
+    /// sets channel variable of call with given uuid
+    ///
+    /// Passing empty value unsets the variable.
+    pub async fn uuid_setvar(&self, uuid: &str, var: &str, value: &str) -> Result<(), EslError> {
+        validate_variable_name(var)?;
+        self.uuid_api(uuid, &format!("uuid_setvar {} {} {}", uuid, var, value))
+            .await?;
+        Ok(())
+    }
+
+    /// sets multiple channel variables of call with given uuid
+    pub async fn uuid_setvar_multi(
+        &self,
+        uuid: &str,
+        variables: &[(&str, &str)],
+    ) -> Result<(), EslError> {
+        let args = setvar_multi_args(variables)?;
+        self.uuid_api(uuid, &format!("uuid_setvar_multi {} {}", uuid, args))
+            .await?;
+        Ok(())
+    }
+
+    /// returns channel variable of call with given uuid
+    pub async fn uuid_getvar(&self, uuid: &str, var: &str) -> Result<Option<String>, EslError> {
+        validate_variable_name(var)?;
+        let value = self
+            .uuid_api(uuid, &format!("uuid_getvar {} {}", uuid, var))
+            .await?;
+        if value == "_undef_" {
+            return Ok(None);
+        }
+        Ok(Some(value))
+    }
+
+    /// returns all channel headers and variables of call with given uuid
+    pub async fn uuid_dump(&self, uuid: &str) -> Result<HashMap<String, String>, EslError> {
+        let dump = self
+            .uuid_api(uuid, &format!("uuid_dump {} json", uuid))
+            .await?;
+        let dump = parse_json_body(&dump)?;
+        Ok(dump
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value.as_str()?.to_string())))
+            .collect())
+    }
+
+    /// starts recording call with given uuid, optionally limited to `limit`
+    ///
+    /// `limit` is rounded up to whole seconds, at least one since `0` means unlimited.
+    pub async fn uuid_record_start(
+        &self,
+        uuid: &str,
+        path: &str,
+        limit: Option<Duration>,
+    ) -> Result<(), EslError> {
+        let command = match limit {
+            Some(limit) => format!(
+                "uuid_record {} start {} {}",
+                uuid,
+                path,
+                whole_seconds(limit).max(1)
+            ),
+            None => format!("uuid_record {} start {}", uuid, path),
+        };
+        self.uuid_api(uuid, &command).await?;
+        Ok(())
+    }
+
+    /// stops recording of call with given uuid, `all` stops every recording
+    pub async fn uuid_record_stop(&self, uuid: &str, path: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_record {} stop {}", uuid, path))
+            .await?;
+        Ok(())
+    }
+
+    /// replaces recorded audio with silence until [`EslConnection::uuid_record_unmask`]
+    pub async fn uuid_record_mask(&self, uuid: &str, path: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_record {} mask {}", uuid, path))
+            .await?;
+        Ok(())
+    }
+
+    /// resumes recording audio masked with [`EslConnection::uuid_record_mask`]
+    pub async fn uuid_record_unmask(&self, uuid: &str, path: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_record {} unmask {}", uuid, path))
+            .await?;
+        Ok(())
+    }
+
+    /// transfers call with given uuid to another extension
+    pub async fn uuid_transfer(&self, uuid: &str, transfer: &Transfer) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_transfer {} {}", uuid, transfer.args()))
+            .await?;
+        Ok(())
+    }
+
+    /// bridges two existing calls
+    pub async fn uuid_bridge(&self, uuid: &str, other_uuid: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_bridge {} {}", uuid, other_uuid))
+            .await?;
+        Ok(())
+    }
+
+    /// stops currently playing file of call with given uuid
+    pub async fn uuid_break(&self, uuid: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_break {}", uuid)).await?;
+        Ok(())
+    }
+
+    /// stops currently playing file and every queued file of call with given uuid
+    pub async fn uuid_break_all(&self, uuid: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_break {} all", uuid))
+            .await?;
+        Ok(())
+    }
+
+    /// places call with given uuid on hold, takes it off hold or toggles hold
+    pub async fn uuid_hold(&self, uuid: &str, action: HoldAction) -> Result<(), EslError> {
+        let command = match action {
+            HoldAction::On => format!("uuid_hold {}", uuid),
+            HoldAction::Off => format!("uuid_hold off {}", uuid),
+            HoldAction::Toggle => format!("uuid_hold toggle {}", uuid),
+        };
+        self.uuid_api(uuid, &command).await?;
+        Ok(())
+    }
+
+    /// plays file to call with given uuid without waiting for it to complete
+    pub(crate) async fn uuid_broadcast(&self, uuid: &str, path: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_broadcast {} {} aleg", uuid, path))
+            .await?;
+        Ok(())
+    }
+
+    /// parks call with given uuid
+    pub async fn uuid_park(&self, uuid: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_park {}", uuid)).await?;
+        Ok(())
+    }
+
+    /// hangs up call with given uuid using given cause
+    pub async fn uuid_kill(&self, uuid: &str, cause: &HangupCause) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_kill {} {}", uuid, cause))
+            .await?;
+        Ok(())
+    }
+
+    /// answers call with given uuid
+    pub async fn uuid_answer(&self, uuid: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_answer {}", uuid))
+            .await?;
+        Ok(())
+    }
+
+    /// pre-answers call with given uuid establishing early media
+    pub async fn uuid_pre_answer(&self, uuid: &str) -> Result<(), EslError> {
+        self.uuid_api(uuid, &format!("uuid_pre_answer {}", uuid))
+            .await?;
+        Ok(())
+    }
+
+    /// returns true if call with given uuid exists
+    pub async fn uuid_exists(&self, uuid: &str) -> Result<bool, EslError> {
+        let exists = self.api(&format!("uuid_exists {}", uuid)).await?;
+        Ok(exists.trim() == "true")
+    }
+
+    /// sends `uuid_*` api command reporting missing channel as [`EslError::NoSuchChannel`]
+    async fn uuid_api(&self, uuid: &str, command: &str) -> Result<String, EslError> {
+        match self.api(command).await {
+            Err(EslError::ApiError(error)) if is_no_such_channel(&error) => {
+                Err(EslError::NoSuchChannel(uuid.to_string()))
+            }
+            response => response,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Action of `uuid_hold`
+pub enum HoldAction {
+    /// Places call on hold
+    On,
+    /// Takes call off hold
+    Off,
+    /// Toggles hold state
+    Toggle,
+}
+
+fn is_no_such_channel(error: &str) -> bool {
+    let error = error.to_lowercase();
+    ["no such channel", "no such session", "invalid session id"]
+        .iter()
+        .any(|message| error.contains(message))
+}
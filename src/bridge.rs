@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use crate::connection::whole_seconds;
+use crate::{EslConnection, EslError, ExecuteResult, HangupCause};
+
+const BRIDGE_APP: &str = "bridge";
+const SUCCESS_DISPOSITION: &str = "SUCCESS";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `bridge` application
+///
+/// Options are set as channel variables of the call before bridging and restored
+/// once bridge completes.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use freeswitch_esl::Bridge;
+///
+/// let options = Bridge::new("user/1000|user/1001")
+///     .ringback("%(2000,4000,440,480)")
+///     .call_timeout(Duration::from_secs(30))
+///     .continue_on_fail(true)
+///     .hangup_after_bridge(false);
+/// ```
+pub struct Bridge {
+    pub(crate) dial_string: String,
+    pub(crate) ringback: Option<String>,
+    pub(crate) continue_on_fail: Option<String>,
+    pub(crate) hangup_after_bridge: Option<bool>,
+    pub(crate) call_timeout: Option<Duration>,
+}
+
+impl Bridge {
+    /// Bridges call to given dial-string e.g. `sofia/gateway/provider/5551234`
+    pub fn new(dial_string: &str) -> Self {
+        Self {
+            dial_string: dial_string.to_string(),
+            ringback: None,
+            continue_on_fail: None,
+            hangup_after_bridge: None,
+            call_timeout: None,
+        }
+    }
+    /// Tone or file played to caller while B-leg is ringing, set as `ringback`
+    pub fn ringback(mut self, ringback: &str) -> Self {
+        self.ringback = Some(ringback.to_string());
+        self
+    }
+    /// Continues dialplan when bridge fails, set as `continue_on_fail`
+    pub fn continue_on_fail(mut self, continue_on_fail: bool) -> Self {
+        self.continue_on_fail = Some(continue_on_fail.to_string());
+        self
+    }
+    /// Continues dialplan only when bridge fails with one of given causes
+    pub fn continue_on_fail_causes(mut self, causes: &[HangupCause]) -> Self {
+        let causes: Vec<&str> = causes.iter().map(HangupCause::as_str).collect();
+        self.continue_on_fail = Some(causes.join(","));
+        self
+    }
+    /// Hangs up caller once B-leg hangs up, set as `hangup_after_bridge`
+    pub fn hangup_after_bridge(mut self, hangup_after_bridge: bool) -> Self {
+        self.hangup_after_bridge = Some(hangup_after_bridge);
+        self
+    }
+    /// Time to wait for B-leg to answer, set as `call_timeout` rounded up to whole seconds
+    pub fn call_timeout(mut self, call_timeout: Duration) -> Self {
+        self.call_timeout = Some(call_timeout);
+        self
+    }
+
+    pub(crate) fn variables(&self) -> Vec<(&'static str, String)> {
+        let mut variables = Vec::new();
+        if let Some(ringback) = &self.ringback {
+            variables.push(("ringback", ringback.clone()));
+        }
+        if let Some(continue_on_fail) = &self.continue_on_fail {
+            variables.push(("continue_on_fail", continue_on_fail.clone()));
+        }
+        if let Some(hangup_after_bridge) = self.hangup_after_bridge {
+            variables.push(("hangup_after_bridge", hangup_after_bridge.to_string()));
+        }
+        if let Some(call_timeout) = self.call_timeout {
+            variables.push(("call_timeout", whole_seconds(call_timeout).to_string()));
+        }
+        variables
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Outcome of `bridge` application
+pub struct BridgeResult {
+    originate_disposition: Option<String>,
+    last_bridge_hangup_cause: Option<HangupCause>,
+    b_leg_uuid: Option<String>,
+    duration: Option<Duration>,
+    result: ExecuteResult,
+}
+
+impl BridgeResult {
+    /// Returns true if B-leg answered and call was bridged
+    pub fn bridged(&self) -> bool {
+        self.originate_disposition.as_deref() == Some(SUCCESS_DISPOSITION)
+    }
+    /// Returns `originate_disposition` e.g. `SUCCESS` or `USER_BUSY`
+    pub fn originate_disposition(&self) -> Option<&str> {
+        self.originate_disposition.as_deref()
+    }
+    /// Returns cause of failed bridge from `originate_disposition`
+    pub fn failure_cause(&self) -> Option<HangupCause> {
+        self.originate_disposition
+            .as_deref()
+            .filter(|disposition| *disposition != SUCCESS_DISPOSITION)
+            .map(HangupCause::from)
+    }
+    /// Returns `last_bridge_hangup_cause`, hangup cause of B-leg
+    pub fn last_bridge_hangup_cause(&self) -> Option<&HangupCause> {
+        self.last_bridge_hangup_cause.as_ref()
+    }
+    /// Returns `Unique-ID` of B-leg from `last_bridge_to`
+    pub fn b_leg_uuid(&self) -> Option<&str> {
+        self.b_leg_uuid.as_deref()
+    }
+    /// Returns how long call was bridged, from `bridge_uepoch` until bridge completed
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+    /// Returns result of the application
+    pub fn execute_result(&self) -> &ExecuteResult {
+        &self.result
+    }
+
+    pub(crate) fn new(result: ExecuteResult) -> Self {
+        let non_empty = |name: &str| {
+            result
+                .variable(name)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let originate_disposition = non_empty("originate_disposition");
+        let last_bridge_hangup_cause =
+            non_empty("last_bridge_hangup_cause").map(|cause| HangupCause::from(cause.as_str()));
+        let b_leg_uuid = non_empty("last_bridge_to").or_else(|| non_empty("bridge_uuid"));
+        let bridge_uepoch = result
+            .variable("bridge_uepoch")
+            .and_then(|uepoch| uepoch.parse::<u64>().ok())
+            .filter(|uepoch| *uepoch > 0);
+        let completed = result
+            .header("Event-Date-Timestamp")
+            .and_then(|timestamp| timestamp.parse::<u64>().ok());
+        let duration = match (bridge_uepoch, completed) {
+            (Some(start), Some(end)) => Some(Duration::from_micros(end.saturating_sub(start))),
+            _ => None,
+        };
+        Self {
+            originate_disposition,
+            last_bridge_hangup_cause,
+            b_leg_uuid,
+            duration,
+            result,
+        }
+    }
+}
+
+impl EslConnection {
+    /// bridges call to dial-string and reports whether B-leg answered
+    ///
+    /// Result is reported even when the call hangs up while bridged, e.g. with
+    /// `hangup_after_bridge`.
+    pub async fn bridge(&self, options: &Bridge) -> Result<BridgeResult, EslError> {
+        let variables = options.variables();
+        let previous = self.previous_variables(&variables).await?;
+        self.set_variables(&variables).await?;
+        let result = self.execute(BRIDGE_APP, &options.dial_string).await;
+        // variables of a hung up call can't leak into later applications
+        let restored = match self.hangup_cause() {
+            Some(_) => Ok(()),
+            None => match self.restore_variables(previous).await {
+                Err(EslError::ChannelHungUp(_)) => Ok(()),
+                restored => restored,
+            },
+        };
+        let result = result?;
+        restored?;
+        Ok(BridgeResult::new(result))
+    }
+}
@@ -0,0 +1,714 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{EslConnection, EslError, Event, HangupCause};
+
+const INFO_SUBCLASS: &str = "callcenter::info";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// How agent is reached when offered a call
+pub enum AgentType {
+    /// Agent is called on its contact for every call
+    Callback,
+    /// Agent is already on a call with given uuid and waits for members
+    UuidStandby,
+}
+
+impl AgentType {
+    /// Returns agent type as expected by `callcenter_config`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Callback => "callback",
+            Self::UuidStandby => "uuid-standby",
+        }
+    }
+}
+
+impl Display for AgentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Availability of agent set by agent or supervisor
+pub enum AgentStatus {
+    /// Agent isn't offered calls
+    LoggedOut,
+    /// Agent is offered calls
+    Available,
+    /// Agent is offered a single call and set to [`AgentStatus::OnBreak`] after it
+    AvailableOnDemand,
+    /// Agent isn't offered calls but stays logged in
+    OnBreak,
+    /// Any other status
+    Other(String),
+}
+
+impl AgentStatus {
+    /// Returns status as reported by `callcenter_config`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::LoggedOut => "Logged Out",
+            Self::Available => "Available",
+            Self::AvailableOnDemand => "Available (On Demand)",
+            Self::OnBreak => "On Break",
+            Self::Other(status) => status,
+        }
+    }
+}
+
+impl From<&str> for AgentStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "Logged Out" => Self::LoggedOut,
+            "Available" => Self::Available,
+            "Available (On Demand)" => Self::AvailableOnDemand,
+            "On Break" => Self::OnBreak,
+            status => Self::Other(status.to_string()),
+        }
+    }
+}
+
+impl Display for AgentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// What agent is currently doing, maintained by mod_callcenter
+pub enum AgentState {
+    /// Agent is in wrap up time or rejected last call
+    Idle,
+    /// Agent is ready to be offered a call
+    Waiting,
+    /// Agent is being offered a call
+    Receiving,
+    /// Agent is talking to a member
+    InAQueueCall,
+    /// Any other state
+    Other(String),
+}
+
+impl AgentState {
+    /// Returns state as reported by `callcenter_config`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Idle => "Idle",
+            Self::Waiting => "Waiting",
+            Self::Receiving => "Receiving",
+            Self::InAQueueCall => "In a queue call",
+            Self::Other(state) => state,
+        }
+    }
+}
+
+impl From<&str> for AgentState {
+    fn from(state: &str) -> Self {
+        match state {
+            "Idle" => Self::Idle,
+            "Waiting" => Self::Waiting,
+            "Receiving" => Self::Receiving,
+            "In a queue call" => Self::InAQueueCall,
+            state => Self::Other(state.to_string()),
+        }
+    }
+}
+
+impl Display for AgentState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// State of agent within a single queue
+pub enum TierState {
+    /// State is not known
+    Unknown,
+    /// Agent didn't answer last call of the queue
+    NoAnswer,
+    /// Agent can be offered calls of the queue
+    Ready,
+    /// Agent is being offered call of the queue
+    Offering,
+    /// Agent is talking to member of the queue
+    ActiveInbound,
+    /// Agent is on standby for the queue
+    Standby,
+    /// Any other state
+    Other(String),
+}
+
+impl TierState {
+    /// Returns state as reported by `callcenter_config`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::NoAnswer => "No Answer",
+            Self::Ready => "Ready",
+            Self::Offering => "Offering",
+            Self::ActiveInbound => "Active Inbound",
+            Self::Standby => "Standby",
+            Self::Other(state) => state,
+        }
+    }
+}
+
+impl From<&str> for TierState {
+    fn from(state: &str) -> Self {
+        match state {
+            "Unknown" => Self::Unknown,
+            "No Answer" => Self::NoAnswer,
+            "Ready" => Self::Ready,
+            "Offering" => Self::Offering,
+            "Active Inbound" => Self::ActiveInbound,
+            "Standby" => Self::Standby,
+            state => Self::Other(state.to_string()),
+        }
+    }
+}
+
+impl Display for TierState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Agent listed by `callcenter_config agent list`
+pub struct Agent {
+    name: String,
+    agent_type: String,
+    contact: String,
+    status: AgentStatus,
+    state: AgentState,
+    max_no_answer: u32,
+    wrap_up_time: Duration,
+    no_answer_count: u32,
+    calls_answered: u32,
+    talk_time: Duration,
+}
+
+impl Agent {
+    /// Returns name of agent e.g. `1000@default`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns type of agent e.g. `callback`
+    pub fn agent_type(&self) -> &str {
+        &self.agent_type
+    }
+    /// Returns dial-string used to reach agent
+    pub fn contact(&self) -> &str {
+        &self.contact
+    }
+    /// Returns availability of agent
+    pub fn status(&self) -> &AgentStatus {
+        &self.status
+    }
+    /// Returns what agent is currently doing
+    pub fn state(&self) -> &AgentState {
+        &self.state
+    }
+    /// Returns number of unanswered calls after which agent is put on break
+    pub fn max_no_answer(&self) -> u32 {
+        self.max_no_answer
+    }
+    /// Returns time agent stays idle after a call
+    pub fn wrap_up_time(&self) -> Duration {
+        self.wrap_up_time
+    }
+    /// Returns number of calls agent didn't answer in a row
+    pub fn no_answer_count(&self) -> u32 {
+        self.no_answer_count
+    }
+    /// Returns number of calls agent answered
+    pub fn calls_answered(&self) -> u32 {
+        self.calls_answered
+    }
+    /// Returns total time agent talked to members
+    pub fn talk_time(&self) -> Duration {
+        self.talk_time
+    }
+
+    fn from_row(row: &HashMap<&str, &str>) -> Option<Self> {
+        let number = |name: &str| row.get(name).and_then(|value| value.parse().ok());
+        let string = |name: &str| row.get(name).copied().unwrap_or_default().to_string();
+        let seconds = |name: &str| {
+            row.get(name)
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or_default()
+        };
+        Some(Self {
+            name: row.get("name")?.to_string(),
+            agent_type: string("type"),
+            contact: string("contact"),
+            status: AgentStatus::from(row.get("status").copied().unwrap_or_default()),
+            state: AgentState::from(row.get("state").copied().unwrap_or_default()),
+            max_no_answer: number("max_no_answer").unwrap_or_default(),
+            wrap_up_time: seconds("wrap_up_time"),
+            no_answer_count: number("no_answer_count").unwrap_or_default(),
+            calls_answered: number("calls_answered").unwrap_or_default(),
+            talk_time: seconds("talk_time"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Tier linking agent to queue, listed by `callcenter_config tier list`
+pub struct Tier {
+    queue: String,
+    agent: String,
+    state: TierState,
+    level: u32,
+    position: u32,
+}
+
+impl Tier {
+    /// Returns name of queue e.g. `support@default`
+    pub fn queue(&self) -> &str {
+        &self.queue
+    }
+    /// Returns name of agent
+    pub fn agent(&self) -> &str {
+        &self.agent
+    }
+    /// Returns state of agent within the queue
+    pub fn state(&self) -> &TierState {
+        &self.state
+    }
+    /// Returns level of tier, lower levels are offered calls first
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+    /// Returns position of agent within its level
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    fn from_row(row: &HashMap<&str, &str>) -> Option<Self> {
+        let number = |name: &str| row.get(name).and_then(|value| value.parse().ok());
+        Some(Self {
+            queue: row.get("queue")?.to_string(),
+            agent: row.get("agent")?.to_string(),
+            state: TierState::from(row.get("state").copied().unwrap_or_default()),
+            level: number("level").unwrap_or_default(),
+            position: number("position").unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Queue listed by `callcenter_config queue list`
+pub struct CallcenterQueue {
+    name: String,
+    strategy: String,
+    moh_sound: Option<String>,
+    calls_answered: u32,
+    calls_abandoned: u32,
+}
+
+impl CallcenterQueue {
+    /// Returns name of queue e.g. `support@default`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns strategy used to offer calls e.g. `longest-idle-agent`
+    pub fn strategy(&self) -> &str {
+        &self.strategy
+    }
+    /// Returns music played to waiting members
+    pub fn moh_sound(&self) -> Option<&str> {
+        self.moh_sound.as_deref()
+    }
+    /// Returns number of calls answered by agents
+    pub fn calls_answered(&self) -> u32 {
+        self.calls_answered
+    }
+    /// Returns number of calls abandoned by members
+    pub fn calls_abandoned(&self) -> u32 {
+        self.calls_abandoned
+    }
+
+    fn from_row(row: &HashMap<&str, &str>) -> Option<Self> {
+        let number = |name: &str| row.get(name).and_then(|value| value.parse().ok());
+        Some(Self {
+            name: row.get("name")?.to_string(),
+            strategy: row.get("strategy").copied().unwrap_or_default().to_string(),
+            moh_sound: row
+                .get("moh_sound")
+                .filter(|sound| !sound.is_empty())
+                .map(ToString::to_string),
+            calls_answered: number("calls_answered").unwrap_or_default(),
+            calls_abandoned: number("calls_abandoned").unwrap_or_default(),
+        })
+    }
+}
+
+/// Parses `|` separated table whose first line names the columns, ends at `+OK`
+fn parse_table(list: &str) -> Vec<HashMap<&str, &str>> {
+    let mut lines = list
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .take_while(|line| *line != "+OK");
+    let Some(columns) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<&str> = columns.split('|').collect();
+    lines
+        .map(|line| columns.iter().copied().zip(line.split('|')).collect())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Kind of `callcenter::info` event from `CC-Action` header
+pub enum CallcenterAction {
+    /// Member entered the queue
+    MemberQueueStart,
+    /// Member left the queue, see [`CallcenterEvent::cause`]
+    MemberQueueEnd,
+    /// Member was put back in the queue
+    MemberQueueResume,
+    /// Agent is being offered a member
+    AgentOffering,
+    /// Agent answered and was bridged to member
+    BridgeAgentStart,
+    /// Bridge of agent and member ended
+    BridgeAgentEnd,
+    /// Agent could not be reached
+    BridgeAgentFail,
+    /// Agent status changed
+    AgentStatusChange,
+    /// Agent state changed
+    AgentStateChange,
+    /// Number of members waiting in queue changed
+    MembersCount,
+    /// Any other action
+    Other(String),
+}
+
+impl CallcenterAction {
+    /// Returns action as reported in `CC-Action` header
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::MemberQueueStart => "member-queue-start",
+            Self::MemberQueueEnd => "member-queue-end",
+            Self::MemberQueueResume => "member-queue-resume",
+            Self::AgentOffering => "agent-offering",
+            Self::BridgeAgentStart => "bridge-agent-start",
+            Self::BridgeAgentEnd => "bridge-agent-end",
+            Self::BridgeAgentFail => "bridge-agent-fail",
+            Self::AgentStatusChange => "agent-status-change",
+            Self::AgentStateChange => "agent-state-change",
+            Self::MembersCount => "members-count",
+            Self::Other(action) => action,
+        }
+    }
+}
+
+impl From<&str> for CallcenterAction {
+    fn from(action: &str) -> Self {
+        match action {
+            "member-queue-start" => Self::MemberQueueStart,
+            "member-queue-end" => Self::MemberQueueEnd,
+            "member-queue-resume" => Self::MemberQueueResume,
+            "agent-offering" => Self::AgentOffering,
+            "bridge-agent-start" => Self::BridgeAgentStart,
+            "bridge-agent-end" => Self::BridgeAgentEnd,
+            "bridge-agent-fail" => Self::BridgeAgentFail,
+            "agent-status-change" => Self::AgentStatusChange,
+            "agent-state-change" => Self::AgentStateChange,
+            "members-count" => Self::MembersCount,
+            action => Self::Other(action.to_string()),
+        }
+    }
+}
+
+impl Display for CallcenterAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Typed `callcenter::info` event
+pub struct CallcenterEvent {
+    action: CallcenterAction,
+    queue: Option<String>,
+    agent: Option<String>,
+    agent_status: Option<AgentStatus>,
+    agent_state: Option<AgentState>,
+    agent_uuid: Option<String>,
+    member_uuid: Option<String>,
+    member_session_uuid: Option<String>,
+    member_caller_id_name: Option<String>,
+    member_caller_id_number: Option<String>,
+    count: Option<u32>,
+    hangup_cause: Option<HangupCause>,
+    cause: Option<String>,
+    event: Event,
+}
+
+impl CallcenterEvent {
+    /// Returns kind of event
+    pub fn action(&self) -> &CallcenterAction {
+        &self.action
+    }
+    /// Returns `CC-Queue`, name of queue
+    pub fn queue(&self) -> Option<&str> {
+        self.queue.as_deref()
+    }
+    /// Returns `CC-Agent`, name of agent
+    pub fn agent(&self) -> Option<&str> {
+        self.agent.as_deref()
+    }
+    /// Returns `CC-Agent-Status` of agent status changes
+    pub fn agent_status(&self) -> Option<&AgentStatus> {
+        self.agent_status.as_ref()
+    }
+    /// Returns `CC-Agent-State` of agent state changes
+    pub fn agent_state(&self) -> Option<&AgentState> {
+        self.agent_state.as_ref()
+    }
+    /// Returns `CC-Agent-UUID`, `Unique-ID` of agent leg
+    pub fn agent_uuid(&self) -> Option<&str> {
+        self.agent_uuid.as_deref()
+    }
+    /// Returns `CC-Member-UUID`, id of member within the queue
+    pub fn member_uuid(&self) -> Option<&str> {
+        self.member_uuid.as_deref()
+    }
+    /// Returns `CC-Member-Session-UUID`, `Unique-ID` of member channel
+    pub fn member_session_uuid(&self) -> Option<&str> {
+        self.member_session_uuid.as_deref()
+    }
+    /// Returns `CC-Member-CID-Name`
+    pub fn member_caller_id_name(&self) -> Option<&str> {
+        self.member_caller_id_name.as_deref()
+    }
+    /// Returns `CC-Member-CID-Number`
+    pub fn member_caller_id_number(&self) -> Option<&str> {
+        self.member_caller_id_number.as_deref()
+    }
+    /// Returns `CC-Count`, number of members waiting in queue
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+    /// Returns `CC-Hangup-Cause` of ended or failed agent bridges
+    pub fn hangup_cause(&self) -> Option<&HangupCause> {
+        self.hangup_cause.as_ref()
+    }
+    /// Returns `CC-Cause` of member leaving queue e.g. `Terminated` or `Cancel`
+    pub fn cause(&self) -> Option<&str> {
+        self.cause.as_deref()
+    }
+    /// Returns raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl TryFrom<Event> for CallcenterEvent {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let headers = event.json_headers().ok_or_else(|| {
+            EslError::InternalError("body was not found in callcenter event".into())
+        })?;
+        let header = |name: &str| headers.get(name).and_then(Value::as_str);
+        if header("Event-Subclass") != Some(INFO_SUBCLASS) {
+            return Err(EslError::InternalError(
+                "event is not callcenter::info".into(),
+            ));
+        }
+        let string = |name: &str| header(name).map(ToString::to_string);
+        Ok(Self {
+            action: CallcenterAction::from(header("CC-Action").unwrap_or_default()),
+            queue: string("CC-Queue"),
+            agent: string("CC-Agent"),
+            agent_status: header("CC-Agent-Status").map(AgentStatus::from),
+            agent_state: header("CC-Agent-State").map(AgentState::from),
+            agent_uuid: string("CC-Agent-UUID"),
+            member_uuid: string("CC-Member-UUID"),
+            member_session_uuid: string("CC-Member-Session-UUID"),
+            member_caller_id_name: string("CC-Member-CID-Name"),
+            member_caller_id_number: string("CC-Member-CID-Number"),
+            count: header("CC-Count").and_then(|count| count.parse().ok()),
+            hangup_cause: header("CC-Hangup-Cause").map(HangupCause::from),
+            cause: string("CC-Cause"),
+            event,
+        })
+    }
+}
+
+#[derive(Debug)]
+/// Stream of `callcenter::info` events, created with [`EslConnection::callcenter_events`]
+pub struct CallcenterEvents {
+    events: UnboundedReceiver<Event>,
+}
+
+impl CallcenterEvents {
+    /// Receives next callcenter event, returns `None` once connection is closed
+    pub async fn recv(&mut self) -> Option<CallcenterEvent> {
+        while let Some(event) = self.events.recv().await {
+            if let Ok(event) = CallcenterEvent::try_from(event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+impl EslConnection {
+    /// returns stream of `callcenter::info` events of every queue
+    pub async fn callcenter_events(&self) -> Result<CallcenterEvents, EslError> {
+        Ok(CallcenterEvents {
+            events: self.custom_events(INFO_SUBCLASS).await?,
+        })
+    }
+
+    /// adds agent, it starts [`AgentStatus::LoggedOut`]
+    pub async fn callcenter_agent_add(
+        &self,
+        name: &str,
+        agent_type: AgentType,
+    ) -> Result<(), EslError> {
+        self.callcenter_api(&format!("agent add {} {}", name, agent_type))
+            .await?;
+        Ok(())
+    }
+
+    /// deletes agent and its tiers
+    pub async fn callcenter_agent_del(&self, name: &str) -> Result<(), EslError> {
+        self.callcenter_api(&format!("agent del {}", name)).await?;
+        Ok(())
+    }
+
+    /// sets availability of agent e.g. logs agent in with [`AgentStatus::Available`]
+    pub async fn callcenter_agent_status(
+        &self,
+        name: &str,
+        status: &AgentStatus,
+    ) -> Result<(), EslError> {
+        self.callcenter_api(&format!("agent set status {} '{}'", name, status))
+            .await?;
+        Ok(())
+    }
+
+    /// sets state of agent
+    pub async fn callcenter_agent_state(
+        &self,
+        name: &str,
+        state: &AgentState,
+    ) -> Result<(), EslError> {
+        self.callcenter_api(&format!("agent set state {} '{}'", name, state))
+            .await?;
+        Ok(())
+    }
+
+    /// sets dial-string used to reach agent
+    pub async fn callcenter_agent_contact(
+        &self,
+        name: &str,
+        contact: &str,
+    ) -> Result<(), EslError> {
+        self.callcenter_api(&format!("agent set contact {} {}", name, contact))
+            .await?;
+        Ok(())
+    }
+
+    /// lists every agent using `callcenter_config agent list`
+    pub async fn callcenter_agents(&self) -> Result<Vec<Agent>, EslError> {
+        let list = self.callcenter_api("agent list").await?;
+        Ok(parse_table(&list)
+            .iter()
+            .filter_map(Agent::from_row)
+            .collect())
+    }
+
+    /// adds agent to queue at given level and position
+    pub async fn callcenter_tier_add(
+        &self,
+        queue: &str,
+        agent: &str,
+        level: u32,
+        position: u32,
+    ) -> Result<(), EslError> {
+        self.callcenter_api(&format!(
+            "tier add {} {} {} {}",
+            queue, agent, level, position
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// removes agent from queue
+    pub async fn callcenter_tier_del(&self, queue: &str, agent: &str) -> Result<(), EslError> {
+        self.callcenter_api(&format!("tier del {} {}", queue, agent))
+            .await?;
+        Ok(())
+    }
+
+    /// sets state of agent within queue
+    pub async fn callcenter_tier_state(
+        &self,
+        queue: &str,
+        agent: &str,
+        state: &TierState,
+    ) -> Result<(), EslError> {
+        self.callcenter_api(&format!("tier set state {} {} '{}'", queue, agent, state))
+            .await?;
+        Ok(())
+    }
+
+    /// lists every tier using `callcenter_config tier list`
+    pub async fn callcenter_tiers(&self) -> Result<Vec<Tier>, EslError> {
+        let list = self.callcenter_api("tier list").await?;
+        Ok(parse_table(&list)
+            .iter()
+            .filter_map(Tier::from_row)
+            .collect())
+    }
+
+    /// lists every loaded queue using `callcenter_config queue list`
+    pub async fn callcenter_queues(&self) -> Result<Vec<CallcenterQueue>, EslError> {
+        let list = self.callcenter_api("queue list").await?;
+        Ok(parse_table(&list)
+            .iter()
+            .filter_map(CallcenterQueue::from_row)
+            .collect())
+    }
+
+    /// returns number of members waiting in queue
+    pub async fn callcenter_queue_count(&self, queue: &str) -> Result<u32, EslError> {
+        let count = self
+            .callcenter_api(&format!("queue count members {}", queue))
+            .await?;
+        Ok(count.trim().parse()?)
+    }
+
+    /// loads queue from configuration
+    pub async fn callcenter_queue_load(&self, queue: &str) -> Result<(), EslError> {
+        self.callcenter_api(&format!("queue load {}", queue))
+            .await?;
+        Ok(())
+    }
+
+    /// reloads queue from configuration
+    pub async fn callcenter_queue_reload(&self, queue: &str) -> Result<(), EslError> {
+        self.callcenter_api(&format!("queue reload {}", queue))
+            .await?;
+        Ok(())
+    }
+
+    async fn callcenter_api(&self, args: &str) -> Result<String, EslError> {
+        self.api(&format!("callcenter_config {}", args)).await
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::EslError;
+
+const MULTISET_DELIMITERS: [char; 6] = [' ', '|', ',', ';', '~', '^'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Direction of call as reported in `Call-Direction` header
+pub enum CallDirection {
+    /// Call received by freeswitch
+    Inbound,
+    /// Call originated by freeswitch
+    Outbound,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Channel variables without `variable_` prefix
+pub struct ChannelVariables {
+    variables: HashMap<String, String>,
+}
+
+impl ChannelVariables {
+    /// Returns value of channel variable
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+    /// Returns channel variable parsed as given type
+    pub fn get_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+    /// Returns true if channel variable is set
+    pub fn contains(&self, name: &str) -> bool {
+        self.variables.contains_key(name)
+    }
+    /// Iterates over all channel variables
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+    /// Returns number of channel variables
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+    /// Returns true if there are no channel variables
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+    pub(crate) fn insert(&mut self, name: impl ToString, value: impl ToString) {
+        self.variables.insert(name.to_string(), value.to_string());
+    }
+    fn clear(&mut self) {
+        self.variables.clear();
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Channel information of call in outbound mode
+///
+/// Parsed from reply of `connect` and refreshed from events of the channel.
+pub struct ChannelData {
+    unique_id: String,
+    channel_name: Option<String>,
+    channel_state: Option<String>,
+    direction: Option<CallDirection>,
+    caller_id_name: Option<String>,
+    caller_id_number: Option<String>,
+    destination_number: Option<String>,
+    context: Option<String>,
+    network_addr: Option<String>,
+    read_codec: Option<String>,
+    write_codec: Option<String>,
+    variables: ChannelVariables,
+    headers: HashMap<String, Value>,
+}
+
+impl ChannelData {
+    /// Returns `Unique-ID` of channel
+    pub fn unique_id(&self) -> &str {
+        &self.unique_id
+    }
+    /// Returns name of channel e.g. `sofia/internal/1000@example.com`
+    pub fn channel_name(&self) -> Option<&str> {
+        self.channel_name.as_deref()
+    }
+    /// Returns state of channel e.g. `CS_EXECUTE`
+    pub fn channel_state(&self) -> Option<&str> {
+        self.channel_state.as_deref()
+    }
+    /// Returns direction of call
+    pub fn direction(&self) -> Option<CallDirection> {
+        self.direction
+    }
+    /// Returns caller id name
+    pub fn caller_id_name(&self) -> Option<&str> {
+        self.caller_id_name.as_deref()
+    }
+    /// Returns caller id number
+    pub fn caller_id_number(&self) -> Option<&str> {
+        self.caller_id_number.as_deref()
+    }
+    /// Returns dialed destination number
+    pub fn destination_number(&self) -> Option<&str> {
+        self.destination_number.as_deref()
+    }
+    /// Returns dialplan context of call
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+    /// Returns network address of caller
+    pub fn network_addr(&self) -> Option<&str> {
+        self.network_addr.as_deref()
+    }
+    /// Returns read codec of channel
+    pub fn read_codec(&self) -> Option<&str> {
+        self.read_codec.as_deref()
+    }
+    /// Returns write codec of channel
+    pub fn write_codec(&self) -> Option<&str> {
+        self.write_codec.as_deref()
+    }
+    /// Returns channel variables
+    pub fn variables(&self) -> &ChannelVariables {
+        &self.variables
+    }
+    /// Returns channel variable without `variable_` prefix
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name)
+    }
+    /// Returns custom SIP header of the INVITE from `variable_sip_h_*`
+    pub fn sip_header(&self, name: &str) -> Option<&str> {
+        self.variables.get(&format!("sip_h_{}", name))
+    }
+    /// Returns all custom SIP headers of the INVITE from `variable_sip_h_*`
+    pub fn sip_headers(&self) -> HashMap<&str, &str> {
+        self.variables
+            .iter()
+            .filter_map(|(name, value)| Some((name.strip_prefix("sip_h_")?, value)))
+            .collect()
+    }
+
+    /// Creates channel data from headers of `connect` reply
+    pub(crate) fn new(unique_id: &str, headers: &HashMap<String, Value>) -> Self {
+        let mut channel_data = Self {
+            unique_id: unique_id.to_string(),
+            headers: headers.clone(),
+            ..Default::default()
+        };
+        channel_data.update(headers);
+        channel_data
+    }
+
+    /// Returns raw header of `connect` reply as refreshed from channel events
+    pub(crate) fn header(&self, name: &str) -> Option<&Value> {
+        self.headers.get(name)
+    }
+
+    /// Caches channel variable which was fetched or set outside of channel events
+    pub(crate) fn set_variable(&mut self, name: &str, value: &str) {
+        self.variables.insert(name, value);
+        self.headers.insert(
+            format!("variable_{}", name),
+            Value::String(value.to_string()),
+        );
+    }
+
+    /// Updates channel data from headers of channel event
+    ///
+    /// CHANNEL_EXECUTE_COMPLETE contains all channel variables, so variables which
+    /// are missing from it were unset.
+    pub(crate) fn update_from_event(&mut self, headers: &HashMap<String, Value>) {
+        let event_name = headers.get("Event-Name").and_then(|name| name.as_str());
+        if event_name == Some("CHANNEL_EXECUTE_COMPLETE") {
+            self.variables.clear();
+            self.headers
+                .retain(|name, _| !name.starts_with("variable_"));
+        }
+        for (name, value) in headers {
+            if name.starts_with("variable_") || self.headers.contains_key(name) {
+                self.headers.insert(name.clone(), value.clone());
+            }
+        }
+        self.update(headers);
+    }
+
+    fn update(&mut self, headers: &HashMap<String, Value>) {
+        for (name, value) in headers {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            if let Some(variable) = name.strip_prefix("variable_") {
+                self.variables.insert(variable, value);
+                continue;
+            }
+            let field = match name.as_str() {
+                "Channel-Name" => &mut self.channel_name,
+                "Channel-State" => &mut self.channel_state,
+                "Caller-Caller-ID-Name" => &mut self.caller_id_name,
+                "Caller-Caller-ID-Number" => &mut self.caller_id_number,
+                "Caller-Destination-Number" => &mut self.destination_number,
+                "Caller-Context" => &mut self.context,
+                "Caller-Network-Addr" => &mut self.network_addr,
+                "Channel-Read-Codec-Name" => &mut self.read_codec,
+                "Channel-Write-Codec-Name" => &mut self.write_codec,
+                "Call-Direction" => {
+                    self.direction = match value {
+                        "inbound" => Some(CallDirection::Inbound),
+                        "outbound" => Some(CallDirection::Outbound),
+                        _ => None,
+                    };
+                    continue;
+                }
+                _ => continue,
+            };
+            *field = Some(value.to_string());
+        }
+    }
+}
+
+pub(crate) fn validate_variable_name(name: &str) -> Result<(), EslError> {
+    if name.is_empty() || name.contains(|c: char| c == '=' || c == ';' || c.is_whitespace()) {
+        return Err(EslError::InvalidVariable(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Formats `var=value` pair for `set`, `export`, `push` and `unshift`
+pub(crate) fn variable_assignment(name: &str, value: &str) -> Result<String, EslError> {
+    validate_variable_name(name)?;
+    Ok(format!("{}={}", name, value))
+}
+
+/// Formats arguments of `multiset` choosing `^^<delimiter>` when values contain spaces
+pub(crate) fn multiset_args(variables: &[(&str, &str)]) -> Result<String, EslError> {
+    let mut assignments = Vec::with_capacity(variables.len());
+    for (name, value) in variables {
+        assignments.push(variable_assignment(name, value)?);
+    }
+    let delimiter = MULTISET_DELIMITERS
+        .into_iter()
+        .find(|delimiter| !assignments.iter().any(|pair| pair.contains(*delimiter)))
+        .ok_or_else(|| EslError::InternalError("Unable to find delimiter for multiset".into()))?;
+    let args = assignments.join(&delimiter.to_string());
+    match delimiter {
+        ' ' => Ok(args),
+        delimiter => Ok(format!("^^{}{}", delimiter, args)),
+    }
+}
+
+/// Formats arguments of `uuid_setvar_multi` escaping `;` inside values
+pub(crate) fn setvar_multi_args(variables: &[(&str, &str)]) -> Result<String, EslError> {
+    let mut assignments = Vec::with_capacity(variables.len());
+    for (name, value) in variables {
+        assignments.push(variable_assignment(name, &value.replace(';', "\\;"))?);
+    }
+    Ok(assignments.join(";"))
+}
@@ -0,0 +1,699 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::event::Event;
+use crate::{EslConnection, EslError};
+
+const MAINTENANCE_SUBCLASS: &str = "conference::maintenance";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// State of conference member
+pub struct MemberFlags {
+    pub(crate) can_hear: bool,
+    pub(crate) can_speak: bool,
+    pub(crate) talking: bool,
+    pub(crate) has_floor: bool,
+    pub(crate) is_moderator: bool,
+    pub(crate) hold: bool,
+    pub(crate) mute_detect: bool,
+}
+
+impl MemberFlags {
+    /// Returns true if member hears the conference
+    pub fn can_hear(&self) -> bool {
+        self.can_hear
+    }
+    /// Returns true if member is not muted
+    pub fn can_speak(&self) -> bool {
+        self.can_speak
+    }
+    /// Returns true if member is currently talking
+    pub fn talking(&self) -> bool {
+        self.talking
+    }
+    /// Returns true if member holds the floor
+    pub fn has_floor(&self) -> bool {
+        self.has_floor
+    }
+    /// Returns true if member joined as moderator
+    pub fn is_moderator(&self) -> bool {
+        self.is_moderator
+    }
+    /// Returns true if member is on hold
+    pub fn hold(&self) -> bool {
+        self.hold
+    }
+    /// Returns true if member talks while muted
+    pub fn mute_detect(&self) -> bool {
+        self.mute_detect
+    }
+
+    /// Parses flags column of `conference list` e.g. `hear|speak|talking|floor`
+    fn from_list(flags: &str) -> Self {
+        let flags: Vec<&str> = flags.split('|').collect();
+        Self {
+            can_hear: flags.contains(&"hear"),
+            can_speak: flags.contains(&"speak"),
+            talking: flags.contains(&"talking"),
+            has_floor: flags.contains(&"floor"),
+            is_moderator: flags.contains(&"moderator"),
+            hold: flags.contains(&"hold"),
+            mute_detect: flags.contains(&"mute_detect"),
+        }
+    }
+
+    /// Parses `flags` object of `json_list`
+    fn from_json(flags: &Value) -> Self {
+        let flag = |name: &str| flags.get(name).and_then(Value::as_bool).unwrap_or(false);
+        Self {
+            can_hear: flag("can_hear"),
+            can_speak: flag("can_speak"),
+            talking: flag("talking"),
+            has_floor: flag("has_floor"),
+            is_moderator: flag("is_moderator"),
+            hold: flag("hold"),
+            mute_detect: flag("mute_detect"),
+        }
+    }
+
+    /// Parses member headers of `conference::maintenance` event
+    fn from_headers(headers: &HashMap<String, Value>) -> Self {
+        let header = |name: &str| headers.get(name).and_then(Value::as_str);
+        let flag = |name: &str| header(name) == Some("true");
+        Self {
+            can_hear: flag("Hear"),
+            can_speak: flag("Speak"),
+            talking: flag("Talking"),
+            has_floor: flag("Floor"),
+            is_moderator: header("Member-Type") == Some("moderator"),
+            hold: flag("Hold"),
+            mute_detect: flag("Mute-Detect"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Member of conference
+pub struct ConferenceMember {
+    pub(crate) id: u32,
+    pub(crate) uuid: String,
+    pub(crate) channel_name: Option<String>,
+    pub(crate) caller_id_name: Option<String>,
+    pub(crate) caller_id_number: Option<String>,
+    pub(crate) flags: MemberFlags,
+    pub(crate) energy: Option<i32>,
+    pub(crate) volume_in: Option<i32>,
+    pub(crate) volume_out: Option<i32>,
+}
+
+impl ConferenceMember {
+    /// Returns member id used by conference commands
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    /// Returns `Unique-ID` of member channel
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    /// Returns name of member channel
+    pub fn channel_name(&self) -> Option<&str> {
+        self.channel_name.as_deref()
+    }
+    /// Returns caller id name of member
+    pub fn caller_id_name(&self) -> Option<&str> {
+        self.caller_id_name.as_deref()
+    }
+    /// Returns caller id number of member
+    pub fn caller_id_number(&self) -> Option<&str> {
+        self.caller_id_number.as_deref()
+    }
+    /// Returns state of member
+    pub fn flags(&self) -> MemberFlags {
+        self.flags
+    }
+    /// Returns energy threshold of member
+    pub fn energy(&self) -> Option<i32> {
+        self.energy
+    }
+    /// Returns input volume of member
+    pub fn volume_in(&self) -> Option<i32> {
+        self.volume_in
+    }
+    /// Returns output volume of member
+    pub fn volume_out(&self) -> Option<i32> {
+        self.volume_out
+    }
+
+    /// Parses member line of `conference list`
+    ///
+    /// `id;channel_name;uuid;caller_id_name;caller_id_number;flags;volume_in;volume_out;energy`
+    fn from_list(line: &str) -> Option<Self> {
+        let columns: Vec<&str> = line.split(';').collect();
+        if columns.len() < 6 {
+            return None;
+        }
+        let non_empty = |index: usize| {
+            columns
+                .get(index)
+                .filter(|column| !column.is_empty())
+                .map(ToString::to_string)
+        };
+        let number = |index: usize| columns.get(index)?.parse().ok();
+        Some(Self {
+            id: columns[0].parse().ok()?,
+            channel_name: non_empty(1),
+            uuid: columns[2].to_string(),
+            caller_id_name: non_empty(3),
+            caller_id_number: non_empty(4),
+            flags: MemberFlags::from_list(columns[5]),
+            volume_in: number(6),
+            volume_out: number(7),
+            energy: number(8),
+        })
+    }
+
+    /// Parses member of `json_list`
+    fn from_json(member: &Value) -> Option<Self> {
+        let string = |name: &str| member.get(name)?.as_str().map(ToString::to_string);
+        let number = |name: &str| {
+            member
+                .get(name)?
+                .as_i64()
+                .and_then(|number| i32::try_from(number).ok())
+        };
+        Some(Self {
+            id: u32::try_from(member.get("id")?.as_u64()?).ok()?,
+            uuid: string("uuid")?,
+            channel_name: None,
+            caller_id_name: string("caller_id_name"),
+            caller_id_number: string("caller_id_number"),
+            flags: member
+                .get("flags")
+                .map(MemberFlags::from_json)
+                .unwrap_or_default(),
+            energy: number("energy"),
+            volume_in: number("volume_in"),
+            volume_out: number("volume_out"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Conference with its members
+pub struct Conference {
+    pub(crate) name: String,
+    pub(crate) uuid: Option<String>,
+    pub(crate) member_count: u32,
+    pub(crate) flags: Vec<String>,
+    pub(crate) members: Vec<ConferenceMember>,
+}
+
+impl Conference {
+    /// Returns name of conference
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns `Conference-Unique-ID`, only reported by `json_list`
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+    /// Returns number of members reported by freeswitch
+    pub fn member_count(&self) -> u32 {
+        self.member_count
+    }
+    /// Returns flags of conference e.g. `running` or `locked`
+    pub fn flags(&self) -> &[String] {
+        &self.flags
+    }
+    /// Returns true if conference is locked
+    pub fn is_locked(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "locked")
+    }
+    /// Returns members of conference
+    pub fn members(&self) -> &[ConferenceMember] {
+        &self.members
+    }
+    /// Returns member with given id
+    pub fn member(&self, id: u32) -> Option<&ConferenceMember> {
+        self.members.iter().find(|member| member.id == id)
+    }
+
+    /// Parses output of `conference list`
+    fn from_list(list: &str) -> Vec<Self> {
+        let mut conferences: Vec<Self> = Vec::new();
+        for line in list.lines() {
+            let line = line.trim_start_matches("+OK ");
+            if let Some(header) = line.strip_prefix("Conference ") {
+                let (name, details) = header.split_once(" (").unwrap_or((header, ""));
+                let member_count = details
+                    .split_whitespace()
+                    .next()
+                    .and_then(|count| count.parse().ok())
+                    .unwrap_or_default();
+                let flags = details
+                    .split_once("flags: ")
+                    .map(|(_, flags)| flags.trim_end_matches(')'))
+                    .map(|flags| {
+                        flags
+                            .split('|')
+                            .filter(|flag| !flag.is_empty())
+                            .map(ToString::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                conferences.push(Self {
+                    name: name.to_string(),
+                    uuid: None,
+                    member_count,
+                    flags,
+                    members: Vec::new(),
+                });
+            } else if let (Some(conference), Some(member)) =
+                (conferences.last_mut(), ConferenceMember::from_list(line))
+            {
+                conference.members.push(member);
+            }
+        }
+        conferences
+    }
+
+    /// Parses conference of `json_list`
+    pub(crate) fn from_json(conference: &Value) -> Option<Self> {
+        let object = conference.as_object()?;
+        let flags = object
+            .iter()
+            .filter(|(_, value)| value.as_bool() == Some(true))
+            .map(|(flag, _)| flag.clone())
+            .collect();
+        let members = object
+            .get("members")
+            .and_then(Value::as_array)
+            .map(|members| {
+                members
+                    .iter()
+                    .filter(|member| {
+                        member.get("type").and_then(Value::as_str) != Some("recording_node")
+                    })
+                    .filter_map(ConferenceMember::from_json)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            name: object.get("conference_name")?.as_str()?.to_string(),
+            uuid: object
+                .get("conference_uuid")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+            member_count: object
+                .get("member_count")
+                .and_then(Value::as_u64)
+                .and_then(|count| u32::try_from(count).ok())
+                .unwrap_or_default(),
+            flags,
+            members,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Members affected by conference command
+pub enum MemberSelector {
+    /// Every member
+    All,
+    /// Member who joined last
+    Last,
+    /// Every member who is not moderator
+    NonModerator,
+    /// Member with given id
+    Id(u32),
+}
+
+impl Display for MemberSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => f.write_str("all"),
+            Self::Last => f.write_str("last"),
+            Self::NonModerator => f.write_str("non_moderator"),
+            Self::Id(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl From<u32> for MemberSelector {
+    fn from(id: u32) -> Self {
+        Self::Id(id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+/// `Action` header of `conference::maintenance` event
+pub enum ConferenceAction {
+    ConferenceCreate,
+    ConferenceDestroy,
+    AddMember,
+    DelMember,
+    StartTalking,
+    StopTalking,
+    MuteMember,
+    UnmuteMember,
+    DeafMember,
+    UndeafMember,
+    KickMember,
+    FloorChange,
+    Lock,
+    Unlock,
+    PlayFile,
+    PlayFileDone,
+    StartRecording,
+    StopRecording,
+    EnergyLevel,
+    Other(String),
+}
+
+impl ConferenceAction {
+    /// Returns action as reported in `Action` header
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::ConferenceCreate => "conference-create",
+            Self::ConferenceDestroy => "conference-destroy",
+            Self::AddMember => "add-member",
+            Self::DelMember => "del-member",
+            Self::StartTalking => "start-talking",
+            Self::StopTalking => "stop-talking",
+            Self::MuteMember => "mute-member",
+            Self::UnmuteMember => "unmute-member",
+            Self::DeafMember => "deaf-member",
+            Self::UndeafMember => "undeaf-member",
+            Self::KickMember => "kick-member",
+            Self::FloorChange => "floor-change",
+            Self::Lock => "lock",
+            Self::Unlock => "unlock",
+            Self::PlayFile => "play-file",
+            Self::PlayFileDone => "play-file-done",
+            Self::StartRecording => "start-recording",
+            Self::StopRecording => "stop-recording",
+            Self::EnergyLevel => "energy-level",
+            Self::Other(action) => action,
+        }
+    }
+}
+
+impl From<&str> for ConferenceAction {
+    fn from(action: &str) -> Self {
+        match action {
+            "conference-create" => Self::ConferenceCreate,
+            "conference-destroy" => Self::ConferenceDestroy,
+            "add-member" => Self::AddMember,
+            "del-member" => Self::DelMember,
+            "start-talking" => Self::StartTalking,
+            "stop-talking" => Self::StopTalking,
+            "mute-member" => Self::MuteMember,
+            "unmute-member" => Self::UnmuteMember,
+            "deaf-member" => Self::DeafMember,
+            "undeaf-member" => Self::UndeafMember,
+            "kick-member" => Self::KickMember,
+            "floor-change" => Self::FloorChange,
+            "lock" => Self::Lock,
+            "unlock" => Self::Unlock,
+            "play-file" => Self::PlayFile,
+            "play-file-done" => Self::PlayFileDone,
+            "start-recording" => Self::StartRecording,
+            "stop-recording" => Self::StopRecording,
+            "energy-level" => Self::EnergyLevel,
+            action => Self::Other(action.to_string()),
+        }
+    }
+}
+
+impl Display for ConferenceAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Parsed `conference::maintenance` event
+pub struct ConferenceEvent {
+    action: ConferenceAction,
+    conference_name: String,
+    conference_uuid: Option<String>,
+    member_id: Option<u32>,
+    unique_id: Option<String>,
+    channel_name: Option<String>,
+    caller_id_name: Option<String>,
+    caller_id_number: Option<String>,
+    flags: Option<MemberFlags>,
+    old_floor: Option<u32>,
+    new_floor: Option<u32>,
+    event: Event,
+}
+
+impl ConferenceEvent {
+    /// Returns what happened in conference
+    pub fn action(&self) -> &ConferenceAction {
+        &self.action
+    }
+    /// Returns `Conference-Name`
+    pub fn conference_name(&self) -> &str {
+        &self.conference_name
+    }
+    /// Returns `Conference-Unique-ID`
+    pub fn conference_uuid(&self) -> Option<&str> {
+        self.conference_uuid.as_deref()
+    }
+    /// Returns `Member-ID` of member event
+    pub fn member_id(&self) -> Option<u32> {
+        self.member_id
+    }
+    /// Returns `Unique-ID` of member channel
+    pub fn unique_id(&self) -> Option<&str> {
+        self.unique_id.as_deref()
+    }
+    /// Returns name of member channel
+    pub fn channel_name(&self) -> Option<&str> {
+        self.channel_name.as_deref()
+    }
+    /// Returns caller id name of member
+    pub fn caller_id_name(&self) -> Option<&str> {
+        self.caller_id_name.as_deref()
+    }
+    /// Returns caller id number of member
+    pub fn caller_id_number(&self) -> Option<&str> {
+        self.caller_id_number.as_deref()
+    }
+    /// Returns state of member after the event
+    pub fn flags(&self) -> Option<MemberFlags> {
+        self.flags
+    }
+    /// Returns `Old-ID` of `floor-change`, `None` when nobody had the floor
+    pub fn old_floor(&self) -> Option<u32> {
+        self.old_floor
+    }
+    /// Returns `New-ID` of `floor-change`, `None` when nobody has the floor
+    pub fn new_floor(&self) -> Option<u32> {
+        self.new_floor
+    }
+    /// Returns raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl TryFrom<Event> for ConferenceEvent {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let headers = event.json_headers().ok_or_else(|| {
+            EslError::InternalError("body was not found in conference event".into())
+        })?;
+        let header = |name: &str| headers.get(name).and_then(Value::as_str);
+        if header("Event-Subclass") != Some(MAINTENANCE_SUBCLASS) {
+            return Err(EslError::InternalError(
+                "event is not conference::maintenance".into(),
+            ));
+        }
+        let string = |name: &str| header(name).map(ToString::to_string);
+        let member_id = header("Member-ID").and_then(|id| id.parse().ok());
+        Ok(Self {
+            action: ConferenceAction::from(header("Action").unwrap_or_default()),
+            conference_name: header("Conference-Name").unwrap_or_default().to_string(),
+            conference_uuid: string("Conference-Unique-ID"),
+            member_id,
+            unique_id: string("Unique-ID"),
+            channel_name: string("Channel-Name"),
+            caller_id_name: string("Caller-Caller-ID-Name"),
+            caller_id_number: string("Caller-Caller-ID-Number"),
+            flags: member_id.map(|_| MemberFlags::from_headers(&headers)),
+            old_floor: header("Old-ID").and_then(|id| id.parse().ok()),
+            new_floor: header("New-ID").and_then(|id| id.parse().ok()),
+            event,
+        })
+    }
+}
+
+#[derive(Debug)]
+/// Stream of `conference::maintenance` events, created with [`EslConnection::conference_events`]
+pub struct ConferenceEvents {
+    events: UnboundedReceiver<Event>,
+}
+
+impl ConferenceEvents {
+    /// Receives next conference event, returns `None` once connection is closed
+    pub async fn recv(&mut self) -> Option<ConferenceEvent> {
+        while let Some(event) = self.events.recv().await {
+            if let Ok(event) = ConferenceEvent::try_from(event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+impl EslConnection {
+    /// returns stream of `conference::maintenance` events of every conference
+    pub async fn conference_events(&self) -> Result<ConferenceEvents, EslError> {
+        Ok(ConferenceEvents {
+            events: self.custom_events(MAINTENANCE_SUBCLASS).await?,
+        })
+    }
+
+    /// lists running conferences and their members using `conference list`
+    pub async fn conference_list(&self) -> Result<Vec<Conference>, EslError> {
+        match self.api("conference list").await {
+            Ok(list) => Ok(Conference::from_list(&list)),
+            Err(EslError::ApiError(error)) if error.contains("No active conferences") => {
+                Ok(Vec::new())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// returns conference with its members using `conference <name> json_list`
+    pub async fn conference(&self, name: &str) -> Result<Conference, EslError> {
+        let list = self.conference_api(name, "json_list").await?;
+        let list: Value = serde_json::from_str(list.trim())?;
+        list.as_array()
+            .and_then(|conferences| conferences.first())
+            .and_then(Conference::from_json)
+            .ok_or_else(|| EslError::ApiError(format!("Conference {} not found", name)))
+    }
+
+    /// mutes members of conference
+    pub async fn conference_mute(
+        &self,
+        name: &str,
+        member: MemberSelector,
+    ) -> Result<(), EslError> {
+        self.conference_api(name, &format!("mute {}", member))
+            .await?;
+        Ok(())
+    }
+
+    /// unmutes members of conference
+    pub async fn conference_unmute(
+        &self,
+        name: &str,
+        member: MemberSelector,
+    ) -> Result<(), EslError> {
+        self.conference_api(name, &format!("unmute {}", member))
+            .await?;
+        Ok(())
+    }
+
+    /// stops members of conference from hearing the conference
+    pub async fn conference_deaf(
+        &self,
+        name: &str,
+        member: MemberSelector,
+    ) -> Result<(), EslError> {
+        self.conference_api(name, &format!("deaf {}", member))
+            .await?;
+        Ok(())
+    }
+
+    /// lets members of conference hear the conference again
+    pub async fn conference_undeaf(
+        &self,
+        name: &str,
+        member: MemberSelector,
+    ) -> Result<(), EslError> {
+        self.conference_api(name, &format!("undeaf {}", member))
+            .await?;
+        Ok(())
+    }
+
+    /// kicks members out of conference
+    pub async fn conference_kick(
+        &self,
+        name: &str,
+        member: MemberSelector,
+    ) -> Result<(), EslError> {
+        self.conference_api(name, &format!("kick {}", member))
+            .await?;
+        Ok(())
+    }
+
+    /// plays file to whole conference or to a single member
+    pub async fn conference_play(
+        &self,
+        name: &str,
+        file: &str,
+        member: Option<u32>,
+    ) -> Result<(), EslError> {
+        let args = match member {
+            Some(member) => format!("play {} {}", file, member),
+            None => format!("play {}", file),
+        };
+        self.conference_api(name, &args).await?;
+        Ok(())
+    }
+
+    /// starts recording conference to given path
+    pub async fn conference_record(&self, name: &str, path: &str) -> Result<(), EslError> {
+        self.conference_api(name, &format!("record {}", path))
+            .await?;
+        Ok(())
+    }
+
+    /// stops recording of conference to given path, `all` stops every recording
+    pub async fn conference_norecord(&self, name: &str, path: &str) -> Result<(), EslError> {
+        self.conference_api(name, &format!("norecord {}", path))
+            .await?;
+        Ok(())
+    }
+
+    /// locks conference so no new members can join
+    pub async fn conference_lock(&self, name: &str) -> Result<(), EslError> {
+        self.conference_api(name, "lock").await?;
+        Ok(())
+    }
+
+    /// unlocks conference
+    pub async fn conference_unlock(&self, name: &str) -> Result<(), EslError> {
+        self.conference_api(name, "unlock").await?;
+        Ok(())
+    }
+
+    /// gives floor of conference to member
+    pub async fn conference_floor(&self, name: &str, member_id: u32) -> Result<(), EslError> {
+        self.conference_api(name, &format!("floor {}", member_id))
+            .await?;
+        Ok(())
+    }
+
+    /// sends `conference <name> <args>` reporting missing conference or member as error
+    ///
+    /// mod_conference doesn't prefix these errors with `-ERR`.
+    async fn conference_api(&self, name: &str, args: &str) -> Result<String, EslError> {
+        let response = self.api(&format!("conference {} {}", name, args)).await?;
+        let trimmed = response.trim();
+        let not_found = trimmed.starts_with("Conference ") && trimmed.ends_with("not found");
+        if not_found || trimmed.starts_with("Non-Existant ID") || trimmed.starts_with("Invalid") {
+            return Err(EslError::ApiError(trimmed.to_string()));
+        }
+        Ok(response)
+    }
+}
@@ -1,52 +1,245 @@
+use crate::channel::ChannelData;
 use crate::code::{Code, ParseCode};
 use crate::error::EslError;
 use crate::esl::EslConnectionType;
-use crate::event::Event;
-use crate::io::EslCodec;
+use crate::event::{parse_json_body, Event};
+use crate::execute::{ExecuteOptions, ExecuteResult};
+use crate::hangup::HangupCause;
+use crate::io::{CommandWithBody, EslCodec};
 use futures::SinkExt;
 use serde::de::DeserializeOwned;
-use serde_json::Value;
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::Ordering;
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{atomic::AtomicBool, Arc, RwLock};
+use std::time::Duration;
 use tokio::io::WriteHalf;
 use tokio::net::TcpStream;
 use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     oneshot::{channel, Sender},
-    Mutex,
+    watch, Mutex,
 };
 use tokio_stream::StreamExt;
-use tokio_util::codec::{FramedRead, FramedWrite};
+use tokio_util::codec::{Encoder, FramedRead, FramedWrite};
 use tracing::trace;
 #[derive(Debug)]
+struct PendingApplication {
+    call_uuid: String,
+    app_name: String,
+    /// CHANNEL_EXECUTE_COMPLETE events left before application is done, one per loop
+    remaining: u32,
+    tx: Sender<Result<Event, EslError>>,
+}
+#[derive(Debug)]
 /// contains Esl connection with freeswitch
 pub struct EslConnection {
     password: String,
     commands: Arc<Mutex<VecDeque<Sender<Event>>>>,
     transport_tx: Arc<Mutex<FramedWrite<WriteHalf<TcpStream>, EslCodec>>>,
     background_jobs: Arc<Mutex<HashMap<String, Sender<Event>>>>,
-    connected: AtomicBool,
+    applications: Arc<Mutex<HashMap<String, PendingApplication>>>,
+    call_listeners: Arc<Mutex<HashMap<String, Vec<UnboundedSender<Event>>>>>,
+    event_listeners: Arc<Mutex<HashMap<String, Vec<UnboundedSender<Event>>>>>,
+    connected: Arc<AtomicBool>,
+    hangup_cause: Option<watch::Receiver<Option<HangupCause>>>,
+    hangup_complete: Option<watch::Receiver<Option<Event>>>,
     pub(crate) call_uuid: Option<String>,
-    connection_info: Option<HashMap<String, Value>>,
+    channel_data: Option<Arc<RwLock<ChannelData>>>,
 }
 
 impl EslConnection {
     /// Returns one of the session parameters as a string
     pub fn get_info_string(&self, key: &str) -> Option<String> {
-        let value = self.connection_info.as_ref()?.get(key)?.clone();
-        serde_json::from_value(value).ok()?
+        self.get_info(key)
     }
 
     /// Returns one of the session parameters as any deserializable type
+    ///
+    /// Session parameters are refreshed from events of the call in outbound mode.
     pub fn get_info<V: DeserializeOwned>(&self, key: &str) -> Option<V> {
-        let value = self.connection_info.as_ref()?.get(key)?.clone();
+        let channel_data = self.channel_data.as_ref()?.read().ok()?;
+        let value = channel_data.header(key)?.clone();
         serde_json::from_value(value).ok()?
     }
 
-    /// returns call uuid in outbound mode
+    /// Returns channel variable of the call
+    ///
+    /// In outbound mode variables are served from the cache kept current by channel
+    /// events. Variables which are not cached are fetched with `uuid_getvar`.
+    pub async fn get_variable(&self, name: &str) -> Result<Option<String>, EslError> {
+        let call_uuid = self.call_uuid.as_ref().ok_or(EslError::NoCallUuid)?;
+        if let Some(channel_data) = &self.channel_data {
+            if let Ok(channel_data) = channel_data.read() {
+                if let Some(value) = channel_data.variable(name) {
+                    return Ok(Some(value.to_string()));
+                }
+            }
+        }
+        let value = self.uuid_getvar(call_uuid, name).await?;
+        if let (Some(channel_data), Some(value)) = (&self.channel_data, &value) {
+            if let Ok(mut channel_data) = channel_data.write() {
+                channel_data.set_variable(name, value);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Returns channel data of call in outbound mode
+    ///
+    /// Channel data is parsed from `connect` reply and refreshed from events of the call.
+    pub fn channel_data(&self) -> Option<ChannelData> {
+        let channel_data = self.channel_data.as_ref()?.read().ok()?;
+        Some(channel_data.clone())
+    }
+
+    /// returns call uuid in outbound mode or of a call handle
     pub async fn call_uuid(&self) -> Option<String> {
         self.call_uuid.clone()
     }
+
+    /// Returns a handle for the call with given `Unique-ID` sharing this connection.
+    ///
+    /// Applications executed through the handle are sent with `sendmsg <uuid>`, so
+    /// calls can be controlled from an inbound connection.
+    pub fn call(&self, uuid: &str) -> EslConnection {
+        Self {
+            password: self.password.clone(),
+            commands: Arc::clone(&self.commands),
+            transport_tx: Arc::clone(&self.transport_tx),
+            background_jobs: Arc::clone(&self.background_jobs),
+            applications: Arc::clone(&self.applications),
+            call_listeners: Arc::clone(&self.call_listeners),
+            event_listeners: Arc::clone(&self.event_listeners),
+            connected: Arc::clone(&self.connected),
+            hangup_cause: None,
+            hangup_complete: None,
+            call_uuid: Some(uuid.to_string()),
+            channel_data: None,
+        }
+    }
+
+    /// Returns receiver of events whose `Unique-ID` matches the call uuid.
+    ///
+    /// Events are demultiplexed on the client side, so only events which are
+    /// subscribed with [`EslConnection::subscribe`] will be received.
+    pub async fn call_events(&self) -> Result<UnboundedReceiver<Event>, EslError> {
+        let call_uuid = self.call_uuid.clone().ok_or(EslError::NoCallUuid)?;
+        let (tx, rx) = unbounded_channel();
+        self.call_listeners
+            .lock()
+            .await
+            .entry(call_uuid)
+            .or_default()
+            .push(tx);
+        Ok(rx)
+    }
+
+    /// Returns receiver of CUSTOM events with given `Event-Subclass` e.g. `conference::maintenance`
+    ///
+    /// Subclass is subscribed with `event json CUSTOM <subclass>`. Outbound connections
+    /// only receive events of their call after `myevents`.
+    pub async fn custom_events(
+        &self,
+        subclass: &str,
+    ) -> Result<UnboundedReceiver<Event>, EslError> {
+        self.subclass_events(&[subclass]).await
+    }
+
+    /// Returns single receiver of CUSTOM events of every given subclass
+    pub(crate) async fn subclass_events(
+        &self,
+        subclasses: &[&str],
+    ) -> Result<UnboundedReceiver<Event>, EslError> {
+        let mut events = vec!["CUSTOM"];
+        events.extend_from_slice(subclasses);
+        self.listen(subclasses, events).await
+    }
+
+    /// Returns single receiver of events with any of given `Event-Name` e.g. `PRESENCE_IN`
+    pub(crate) async fn named_events(
+        &self,
+        names: &[&str],
+    ) -> Result<UnboundedReceiver<Event>, EslError> {
+        self.listen(names, names.to_vec()).await
+    }
+
+    /// Registers listener of events with given subclass or name and subscribes them
+    async fn listen(
+        &self,
+        keys: &[&str],
+        events: Vec<&str>,
+    ) -> Result<UnboundedReceiver<Event>, EslError> {
+        let (tx, rx) = unbounded_channel();
+        let mut event_listeners = self.event_listeners.lock().await;
+        for key in keys {
+            event_listeners
+                .entry(key.to_string())
+                .or_default()
+                .push(tx.clone());
+        }
+        drop(event_listeners);
+        let response = self.subscribe(events).await?;
+        parse_command_reply(&response)?;
+        Ok(rx)
+    }
+
+    /// Returns hangup cause once call is hung up in outbound mode
+    pub fn hangup_cause(&self) -> Option<HangupCause> {
+        self.hangup_cause.as_ref()?.borrow().clone()
+    }
+
+    /// Waits for call to hang up in outbound mode and returns hangup cause
+    pub async fn on_hangup(&self) -> Result<HangupCause, EslError> {
+        let mut hangup_cause = self.hangup_cause.clone().ok_or_else(|| {
+            EslError::InternalError("hangup is only tracked in outbound mode".into())
+        })?;
+        let hangup_cause = hangup_cause
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|error| EslError::InternalError(error.to_string()))?;
+        Ok(hangup_cause.clone().unwrap_or(HangupCause::None))
+    }
+
+    /// Waits for CHANNEL_HANGUP_COMPLETE of call in outbound mode
+    ///
+    /// The event is only received before the socket is closed when linger is enabled
+    /// with [`EslConnection::linger`] or [`OutboundOptions::linger`](crate::OutboundOptions::linger).
+    pub async fn on_hangup_complete(&self) -> Result<Event, EslError> {
+        let mut hangup_complete = self.hangup_complete.clone().ok_or_else(|| {
+            EslError::InternalError("hangup is only tracked in outbound mode".into())
+        })?;
+        let event = hangup_complete
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|_| {
+                EslError::ConnectionError("connection closed before CHANNEL_HANGUP_COMPLETE".into())
+            })?;
+        event
+            .clone()
+            .ok_or_else(|| EslError::InternalError("CHANNEL_HANGUP_COMPLETE was not found".into()))
+    }
+
+    /// Keeps socket open after hangup so remaining events are received in outbound mode
+    ///
+    /// Socket is closed after `timeout` or when the session is destroyed. FreeSWITCH
+    /// takes whole seconds, so `timeout` is rounded up.
+    pub async fn linger(&self, timeout: Option<Duration>) -> Result<(), EslError> {
+        let command = match timeout {
+            Some(timeout) => format!("linger {}", whole_seconds(timeout)),
+            None => "linger".to_string(),
+        };
+        let response = self.send_recv(command.as_bytes()).await?;
+        parse_command_reply(&response)?;
+        Ok(())
+    }
+
+    /// Disables linger so socket is closed as soon as call is hung up
+    pub async fn nolinger(&self) -> Result<(), EslError> {
+        let response = self.send_recv(b"nolinger").await?;
+        parse_command_reply(&response)?;
+        Ok(())
+    }
+
     /// disconnects from freeswitch
     pub async fn disconnect(self) -> Result<(), EslError> {
         self.send_recv(b"exit").await?;
@@ -57,15 +250,36 @@ impl EslConnection {
     pub fn connected(&self) -> bool {
         self.connected.load(Ordering::Relaxed)
     }
-    pub(crate) async fn send(&self, item: &[u8]) -> Result<(), EslError> {
-        let mut transport = self.transport_tx.lock().await;
-        transport.send(item).await
-    }
     /// sends raw message to freeswitch and receives reply
     pub async fn send_recv(&self, item: &[u8]) -> Result<Event, EslError> {
-        self.send(item).await?;
-        let (tx, rx) = channel();
-        self.commands.lock().await.push_back(tx);
+        self.send_queued(item).await
+    }
+
+    /// sends command followed by body of `Content-Length` bytes and receives reply
+    pub(crate) async fn send_recv_with_body(
+        &self,
+        command: &[u8],
+        body: &[u8],
+    ) -> Result<Event, EslError> {
+        self.send_queued(CommandWithBody { command, body }).await
+    }
+
+    /// sends item and receives reply matched by order of sends
+    async fn send_queued<I>(&self, item: I) -> Result<Event, EslError>
+    where
+        EslCodec: Encoder<I, Error = EslError>,
+    {
+        let rx = {
+            // reply is queued while transport is held, so concurrent callers can't swap replies
+            let mut transport = self.transport_tx.lock().await;
+            let (tx, rx) = channel();
+            self.commands.lock().await.push_back(tx);
+            if let Err(error) = transport.send(item).await {
+                self.commands.lock().await.pop_back();
+                return Err(error);
+            }
+            rx
+        };
         Ok(rx.await?)
     }
 
@@ -74,89 +288,225 @@ impl EslConnection {
         password: impl ToString,
         connection_type: EslConnectionType,
     ) -> Result<Self, EslError> {
-        // let sender = Arc::new(sender);
         let commands = Arc::new(Mutex::new(VecDeque::new()));
         let inner_commands = Arc::clone(&commands);
         let background_jobs = Arc::new(Mutex::new(HashMap::new()));
         let inner_background_jobs = Arc::clone(&background_jobs);
+        let applications = Arc::new(Mutex::new(HashMap::new()));
+        let inner_applications = Arc::clone(&applications);
+        let call_listeners = Arc::new(Mutex::new(HashMap::new()));
+        let inner_call_listeners = Arc::clone(&call_listeners);
+        let event_listeners = Arc::new(Mutex::new(HashMap::new()));
+        let inner_event_listeners = Arc::clone(&event_listeners);
+        let connected = Arc::new(AtomicBool::new(false));
+        let inner_connected = Arc::clone(&connected);
+        let (hangup_tx, hangup_rx) = watch::channel(None);
+        let (hangup_complete_tx, hangup_complete_rx) = watch::channel(None);
         let esl_codec = EslCodec {};
         let (read_half, write_half) = tokio::io::split(stream);
         let mut transport_rx = FramedRead::new(read_half, esl_codec.clone());
         let transport_tx = Arc::new(Mutex::new(FramedWrite::new(write_half, esl_codec.clone())));
-        if connection_type == EslConnectionType::Inbound {
-            transport_rx.next().await;
+        let mut call_uuid = None;
+        let mut channel_data = None;
+        match connection_type {
+            EslConnectionType::Inbound => {
+                transport_rx.next().await;
+            }
+            EslConnectionType::Outbound => {
+                transport_tx.lock().await.send(&b"connect"[..]).await?;
+                let response = transport_rx.next().await.ok_or_else(|| {
+                    EslError::ConnectionError("connection closed before connect reply".into())
+                })??;
+                trace!("{:?}", response);
+                let channel_unique_id = response
+                    .headers()
+                    .get("Channel-Unique-ID")
+                    .and_then(|channel_unique_id| channel_unique_id.as_str())
+                    .ok_or_else(|| {
+                        EslError::InternalError("Channel-Unique-ID was not found".into())
+                    })?;
+                call_uuid = Some(channel_unique_id.to_string());
+                let data = ChannelData::new(channel_unique_id, response.headers());
+                channel_data = Some(Arc::new(RwLock::new(data)));
+                connected.store(true, Ordering::Relaxed);
+            }
         }
-        let mut connection = Self {
+        let outbound_call_uuid = call_uuid.clone();
+        let inner_channel_data = channel_data.clone();
+        let connection = Self {
             password: password.to_string(),
             commands,
             background_jobs,
+            applications,
+            call_listeners,
+            event_listeners,
             transport_tx,
-            connected: AtomicBool::new(false),
-            call_uuid: None,
-            connection_info: None,
+            connected,
+            hangup_cause: call_uuid.as_ref().map(|_| hangup_rx),
+            hangup_complete: call_uuid.as_ref().map(|_| hangup_complete_rx),
+            call_uuid,
+            channel_data,
         };
         tokio::spawn(async move {
-            loop {
-                if let Some(Ok(event)) = transport_rx.next().await {
-                    if let Some(event_type) = event.headers.get("Content-Type") {
-                        match event_type.as_str().unwrap() {
-                            "text/disconnect-notice" => {
-                                trace!("got disconnect notice");
-                                return;
+            while let Some(Ok(event)) = transport_rx.next().await {
+                if let Some(event_type) = event.headers.get("Content-Type") {
+                    match event_type.as_str().unwrap() {
+                        "text/disconnect-notice" => {
+                            let disposition = event
+                                .headers
+                                .get("Content-Disposition")
+                                .and_then(|disposition| disposition.as_str());
+                            if disposition == Some("linger") {
+                                trace!("got linger disconnect notice");
+                                continue;
                             }
-                            "text/event-json" => {
-                                trace!("got event-json");
-                                let data = event
-                                    .body()
-                                    .clone()
-                                    .expect("Unable to get body of event-json");
-
-                                let event_body = parse_json_body(&data)
-                                    .expect("Unable to parse body of event-json");
-                                let job_uuid = event_body.get("Job-UUID");
-                                if let Some(job_uuid) = job_uuid {
-                                    let job_uuid = job_uuid.as_str().unwrap();
-                                    if let Some(tx) =
-                                        inner_background_jobs.lock().await.remove(job_uuid)
+                            trace!("got disconnect notice");
+                            break;
+                        }
+                        "text/event-json" => {
+                            trace!("got event-json");
+                            let data = event
+                                .body()
+                                .clone()
+                                .expect("Unable to get body of event-json");
+
+                            let event_body =
+                                parse_json_body(&data).expect("Unable to parse body of event-json");
+                            let event_name = event_body
+                                .get("Event-Name")
+                                .and_then(|event_name| event_name.as_str());
+                            // CUSTOM events are listened to by subclass, others by name
+                            let listener_key = match event_name {
+                                Some("CUSTOM") => event_body
+                                    .get("Event-Subclass")
+                                    .and_then(|subclass| subclass.as_str()),
+                                event_name => event_name,
+                            };
+                            if let Some(key) = listener_key {
+                                let mut event_listeners = inner_event_listeners.lock().await;
+                                if let Some(listeners) = event_listeners.get_mut(key) {
+                                    listeners.retain(|tx| tx.send(event.clone()).is_ok());
+                                    if listeners.is_empty() {
+                                        event_listeners.remove(key);
+                                    }
+                                }
+                            }
+                            if let Some(unique_id) =
+                                event_body.get("Unique-ID").and_then(|id| id.as_str())
+                            {
+                                let mut call_listeners = inner_call_listeners.lock().await;
+                                if let Some(listeners) = call_listeners.get_mut(unique_id) {
+                                    listeners.retain(|tx| tx.send(event.clone()).is_ok());
+                                    if listeners.is_empty() {
+                                        call_listeners.remove(unique_id);
+                                    }
+                                }
+                                drop(call_listeners);
+                                if outbound_call_uuid.as_deref() == Some(unique_id) {
+                                    if let Some(Ok(mut channel_data)) =
+                                        inner_channel_data.as_ref().map(|data| data.write())
                                     {
-                                        tx.send(event)
-                                            .expect("Unable to send channel message from bgapi");
+                                        channel_data.update_from_event(&event_body);
                                     }
-                                    trace!("continued");
-                                    continue;
                                 }
-                                if let Some(application_uuid) = event_body.get("Application-UUID") {
-                                    let job_uuid = application_uuid.as_str().unwrap();
-                                    if let Some(event_name) = event_body.get("Event-Name") {
-                                        if let Some(event_name) = event_name.as_str() {
-                                            if event_name == "CHANNEL_EXECUTE_COMPLETE" {
-                                                if let Some(tx) = inner_background_jobs
-                                                    .lock()
-                                                    .await
-                                                    .remove(job_uuid)
-                                                {
-                                                    tx.send(event).expect(
-                                                        "Unable to send channel message from bgapi",
-                                                    );
-                                                }
-                                                trace!("continued");
-                                                trace!("got channel execute complete");
-                                            }
+                                if event_name == Some("CHANNEL_HANGUP") {
+                                    let hangup_cause = event_body
+                                        .get("Hangup-Cause")
+                                        .and_then(|hangup_cause| hangup_cause.as_str())
+                                        .map(HangupCause::from)
+                                        .unwrap_or(HangupCause::None);
+                                    trace!("call {} hung up with {}", unique_id, hangup_cause);
+                                    let mut applications = inner_applications.lock().await;
+                                    let hungup: Vec<String> = applications
+                                        .iter()
+                                        // bridge still fires CHANNEL_EXECUTE_COMPLETE with its
+                                        // result when the call hangs up while bridged
+                                        .filter(|(_, application)| {
+                                            application.call_uuid == unique_id
+                                                && application.app_name != "bridge"
+                                        })
+                                        .map(|(event_uuid, _)| event_uuid.clone())
+                                        .collect();
+                                    for event_uuid in hungup {
+                                        if let Some(application) = applications.remove(&event_uuid)
+                                        {
+                                            // hangup app completes only after CHANNEL_HANGUP
+                                            let result = if application.app_name == "hangup" {
+                                                Ok(event.clone())
+                                            } else {
+                                                Err(EslError::ChannelHungUp(hangup_cause.clone()))
+                                            };
+                                            let _ = application.tx.send(result);
                                         }
                                     }
+                                    if outbound_call_uuid.as_deref() == Some(unique_id) {
+                                        hangup_tx.send_replace(Some(hangup_cause));
+                                    }
+                                }
+                                if event_name == Some("CHANNEL_HANGUP_COMPLETE")
+                                    && outbound_call_uuid.as_deref() == Some(unique_id)
+                                {
+                                    hangup_complete_tx.send_replace(Some(event.clone()));
                                 }
+                            }
+                            let job_uuid = event_body.get("Job-UUID");
+                            if let Some(job_uuid) = job_uuid {
+                                let job_uuid = job_uuid.as_str().unwrap();
+                                if let Some(tx) =
+                                    inner_background_jobs.lock().await.remove(job_uuid)
+                                {
+                                    let _ = tx.send(event);
+                                }
+                                trace!("continued");
                                 continue;
                             }
-                            _ => {
-                                trace!("got another event {:?}", event);
+                            if let Some(application_uuid) = event_body.get("Application-UUID") {
+                                let job_uuid = application_uuid.as_str().unwrap();
+                                if event_name == Some("CHANNEL_EXECUTE_COMPLETE") {
+                                    let mut applications = inner_applications.lock().await;
+                                    if let Some(application) = applications.get_mut(job_uuid) {
+                                        application.remaining -= 1;
+                                        if application.remaining == 0 {
+                                            if let Some(application) = applications.remove(job_uuid)
+                                            {
+                                                let _ = application.tx.send(Ok(event));
+                                            }
+                                        }
+                                    }
+                                    trace!("got channel execute complete");
+                                }
                             }
+                            continue;
+                        }
+                        _ => {
+                            trace!("got another event {:?}", event);
                         }
                     }
-                    if let Some(tx) = inner_commands.lock().await.pop_front() {
-                        tx.send(event).expect("msg");
-                    }
+                }
+                if let Some(tx) = inner_commands.lock().await.pop_front() {
+                    let _ = tx.send(event);
                 }
             }
+            trace!("connection with freeswitch closed");
+            inner_connected.store(false, Ordering::Relaxed);
+            if outbound_call_uuid.is_some() {
+                hangup_tx.send_if_modified(|hangup_cause| {
+                    hangup_cause.get_or_insert(HangupCause::None);
+                    true
+                });
+            }
+            let hangup_cause = hangup_tx.borrow().clone();
+            for (_, application) in inner_applications.lock().await.drain() {
+                if let Some(hangup_cause) = &hangup_cause {
+                    let _ = application
+                        .tx
+                        .send(Err(EslError::ChannelHungUp(hangup_cause.clone())));
+                }
+            }
+            inner_background_jobs.lock().await.clear();
+            inner_commands.lock().await.clear();
+            inner_call_listeners.lock().await.clear();
+            inner_event_listeners.lock().await.clear();
         });
         match connection_type {
             EslConnectionType::Inbound => {
@@ -167,23 +517,21 @@ impl EslConnection {
                     .await?;
             }
             EslConnectionType::Outbound => {
-                let response = connection.send_recv(b"connect").await?;
-                trace!("{:?}", response);
-                connection.connection_info = Some(response.headers().clone());
                 let response = connection
-                    .subscribe(vec!["BACKGROUND_JOB", "CHANNEL_EXECUTE_COMPLETE"])
+                    .subscribe(vec![
+                        "BACKGROUND_JOB",
+                        "CHANNEL_EXECUTE_COMPLETE",
+                        "CHANNEL_HANGUP",
+                        "CHANNEL_HANGUP_COMPLETE",
+                        "DTMF",
+                        "PLAYBACK_STOP",
+                        "RECORD_START",
+                        "RECORD_STOP",
+                    ])
                     .await?;
                 trace!("{:?}", response);
                 let response = connection.send_recv(b"myevents").await?;
                 trace!("{:?}", response);
-                let connection_info = connection.connection_info.as_ref().unwrap();
-
-                let channel_unique_id = connection_info
-                    .get("Channel-Unique-ID")
-                    .unwrap()
-                    .as_str()
-                    .unwrap();
-                connection.call_uuid = Some(channel_unique_id.to_string());
             }
         }
         Ok(connection)
@@ -218,29 +566,84 @@ impl EslConnection {
     }
 
     /// For hanging up call in outbound mode
-    pub async fn hangup(&self, reason: &str) -> Result<Event, EslError> {
+    ///
+    /// FreeSWITCH fires CHANNEL_HANGUP before the `hangup` application completes, so
+    /// result is built from the CHANNEL_HANGUP event.
+    pub async fn hangup(&self, reason: &str) -> Result<ExecuteResult, EslError> {
         self.execute("hangup", reason).await
     }
 
-    /// executes application in freeswitch
-    pub async fn execute(&self, app_name: &str, app_args: &str) -> Result<Event, EslError> {
+    /// executes application in freeswitch on the call uuid of this connection
+    ///
+    /// In inbound mode use [`EslConnection::call`] to get a connection for the call.
+    pub async fn execute(&self, app_name: &str, app_args: &str) -> Result<ExecuteResult, EslError> {
+        self.execute_with(app_name, app_args, &ExecuteOptions::default())
+            .await
+    }
+
+    /// executes application with given options and waits for CHANNEL_EXECUTE_COMPLETE
+    ///
+    /// With [`ExecuteOptions::loops`] every iteration fires CHANNEL_EXECUTE_COMPLETE,
+    /// result of the last iteration is returned once all of them completed.
+    pub async fn execute_with(
+        &self,
+        app_name: &str,
+        app_args: &str,
+        options: &ExecuteOptions,
+    ) -> Result<ExecuteResult, EslError> {
+        let call_uuid = self.call_uuid.as_ref().ok_or(EslError::NoCallUuid)?;
         let event_uuid = uuid::Uuid::new_v4().to_string();
         let (tx, rx) = channel();
-        self.background_jobs
-            .lock()
-            .await
-            .insert(event_uuid.clone(), tx);
-        let call_uuid = self.call_uuid.as_ref().unwrap().clone();
-        let command  = format!("sendmsg {}\nexecute-app-name: {}\nexecute-app-arg: {}\ncall-command: execute\nEvent-UUID: {}",call_uuid,app_name,app_args,event_uuid);
+        self.applications.lock().await.insert(
+            event_uuid.clone(),
+            PendingApplication {
+                call_uuid: call_uuid.clone(),
+                app_name: app_name.to_string(),
+                remaining: options.loops.unwrap_or(1).max(1),
+                tx,
+            },
+        );
+        let command = options.sendmsg(call_uuid, app_name, app_args, &event_uuid);
         let response = self.send_recv(command.as_bytes()).await?;
         trace!("inside execute {:?}", response);
-        let resp = rx.await?;
+        if let Err(error) = parse_command_reply(&response) {
+            self.applications.lock().await.remove(&event_uuid);
+            return Err(error);
+        }
+        let resp = rx.await??;
         trace!("got response from channel {:?}", resp);
-        Ok(resp)
+        ExecuteResult::try_from(resp)
+    }
+
+    /// executes application without waiting for it to complete
+    ///
+    /// Returns the `Application-UUID` of the CHANNEL_EXECUTE_COMPLETE event which
+    /// will be fired once application completes. The uuid is informational only,
+    /// completion is not tracked; match it against events from
+    /// [`EslConnection::call_events`] to follow the application.
+    pub async fn execute_async(&self, app_name: &str, app_args: &str) -> Result<String, EslError> {
+        self.execute_async_with(app_name, app_args, &ExecuteOptions::default())
+            .await
+    }
+
+    /// executes application with given options without waiting for it to complete
+    pub async fn execute_async_with(
+        &self,
+        app_name: &str,
+        app_args: &str,
+        options: &ExecuteOptions,
+    ) -> Result<String, EslError> {
+        let call_uuid = self.call_uuid.as_ref().ok_or(EslError::NoCallUuid)?;
+        let event_uuid = uuid::Uuid::new_v4().to_string();
+        let command = options.sendmsg(call_uuid, app_name, app_args, &event_uuid);
+        let response = self.send_recv(command.as_bytes()).await?;
+        trace!("inside execute_async {:?}", response);
+        parse_command_reply(&response)?;
+        Ok(event_uuid)
     }
 
     /// answers call in outbound mode
-    pub async fn answer(&self) -> Result<Event, EslError> {
+    pub async fn answer(&self) -> Result<ExecuteResult, EslError> {
         self.execute("answer", "").await
     }
 
@@ -296,9 +699,7 @@ impl EslConnection {
     }
 }
 fn parse_api_response(body: &str) -> Result<(Code, String), EslError> {
-    let space_index = body
-        .find(char::is_whitespace)
-        .ok_or_else(|| EslError::InternalError("Unable to find space index".into()))?;
+    let space_index = body.find(char::is_whitespace).unwrap_or(body.len());
     let code = &body[..space_index];
     let text_start = space_index + 1;
     let body_length = body.len();
@@ -310,6 +711,20 @@ fn parse_api_response(body: &str) -> Result<(Code, String), EslError> {
     let code = code.parse_code()?;
     Ok((code, text))
 }
-fn parse_json_body(body: &str) -> Result<HashMap<String, Value>, EslError> {
-    Ok(serde_json::from_str(body)?)
+/// Rounds duration up to whole seconds taken by FreeSWITCH, so short durations aren't `0`
+pub(crate) fn whole_seconds(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+pub(crate) fn parse_command_reply(event: &Event) -> Result<String, EslError> {
+    let reply_text = event
+        .headers()
+        .get("Reply-Text")
+        .and_then(|reply_text| reply_text.as_str())
+        .ok_or_else(|| {
+            EslError::InternalError("Reply-Text in command reply was not found".into())
+        })?;
+    match reply_text.strip_prefix("-ERR") {
+        Some(text) => Err(EslError::ApiError(text.trim().to_string())),
+        None => Ok(reply_text.trim_start_matches("+OK").trim().to_string()),
+    }
 }
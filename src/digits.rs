@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use crate::ExecuteResult;
+
+const PAGD_VARIABLE_NAME: &str = "pagd_digits";
+const READ_VARIABLE_NAME: &str = "read_digits";
+const DEFAULT_INVALID_FILE: &str = "silence_stream://250";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `play_and_get_digits` application
+///
+/// ```rust
+/// use std::time::Duration;
+/// use freeswitch_esl::PlayAndGetDigits;
+///
+/// let options = PlayAndGetDigits::new("conference/conf-pin.wav")
+///     .min(4)
+///     .max(6)
+///     .invalid_file("conference/conf-bad-pin.wav")
+///     .digits_regex("^\\d+$")
+///     .timeout(Duration::from_secs(5));
+/// ```
+pub struct PlayAndGetDigits {
+    pub(crate) min: u8,
+    pub(crate) max: u8,
+    pub(crate) tries: u8,
+    pub(crate) timeout: Duration,
+    pub(crate) terminators: String,
+    pub(crate) file: String,
+    pub(crate) invalid_file: String,
+    pub(crate) variable_name: String,
+    pub(crate) digits_regex: Option<String>,
+    pub(crate) digit_timeout: Option<Duration>,
+    pub(crate) transfer_on_failure: Option<String>,
+}
+
+impl PlayAndGetDigits {
+    /// Collects single digit after playing `file` with 3 tries, 5 second timeout and `#` terminator
+    pub fn new(file: &str) -> Self {
+        Self {
+            min: 1,
+            max: 1,
+            tries: 3,
+            timeout: Duration::from_secs(5),
+            terminators: "#".to_string(),
+            file: file.to_string(),
+            invalid_file: DEFAULT_INVALID_FILE.to_string(),
+            variable_name: PAGD_VARIABLE_NAME.to_string(),
+            digits_regex: None,
+            digit_timeout: None,
+            transfer_on_failure: None,
+        }
+    }
+    /// Minimum number of digits to collect
+    pub fn min(mut self, min: u8) -> Self {
+        self.min = min;
+        self
+    }
+    /// Maximum number of digits to collect
+    pub fn max(mut self, max: u8) -> Self {
+        self.max = max;
+        self
+    }
+    /// Number of times prompt is played when input is invalid or missing
+    pub fn tries(mut self, tries: u8) -> Self {
+        self.tries = tries;
+        self
+    }
+    /// Time to wait for first digit after prompt is played
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    /// Digits which end input e.g. `#`, `none` disables terminators
+    pub fn terminators(mut self, terminators: &str) -> Self {
+        self.terminators = terminators.to_string();
+        self
+    }
+    /// File played when input doesn't match `digits_regex`
+    pub fn invalid_file(mut self, invalid_file: &str) -> Self {
+        self.invalid_file = invalid_file.to_string();
+        self
+    }
+    /// Channel variable where collected digits are stored, cleared before collecting
+    pub fn variable_name(mut self, variable_name: &str) -> Self {
+        self.variable_name = variable_name.to_string();
+        self
+    }
+    /// Regular expression collected digits have to match
+    pub fn digits_regex(mut self, digits_regex: &str) -> Self {
+        self.digits_regex = Some(digits_regex.to_string());
+        self
+    }
+    /// Time to wait between digits, defaults to `timeout`
+    pub fn digit_timeout(mut self, digit_timeout: Duration) -> Self {
+        self.digit_timeout = Some(digit_timeout);
+        self
+    }
+    /// Transfers call to `extension dialplan context` when all tries fail
+    pub fn transfer_on_failure(mut self, transfer_on_failure: &str) -> Self {
+        self.transfer_on_failure = Some(transfer_on_failure.to_string());
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        let mut args = format!(
+            "{} {} {} {} {} {} {} {}",
+            self.min,
+            self.max,
+            self.tries,
+            self.timeout.as_millis(),
+            self.terminators,
+            self.file,
+            self.invalid_file,
+            self.variable_name
+        );
+        if self.digits_regex.is_none()
+            && self.digit_timeout.is_none()
+            && self.transfer_on_failure.is_none()
+        {
+            return args;
+        }
+        args.push(' ');
+        args.push_str(self.digits_regex.as_deref().unwrap_or(".*"));
+        if self.digit_timeout.is_none() && self.transfer_on_failure.is_none() {
+            return args;
+        }
+        let digit_timeout = self.digit_timeout.unwrap_or(self.timeout);
+        args.push_str(&format!(" {}", digit_timeout.as_millis()));
+        if let Some(transfer_on_failure) = &self.transfer_on_failure {
+            args.push_str(&format!(" {}", transfer_on_failure));
+        }
+        args
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `read` application
+pub struct ReadDigits {
+    pub(crate) min: u8,
+    pub(crate) max: u8,
+    pub(crate) file: String,
+    pub(crate) variable_name: String,
+    pub(crate) timeout: Duration,
+    pub(crate) terminators: String,
+}
+
+impl ReadDigits {
+    /// Collects single digit after playing `file` with 5 second timeout and `#` terminator
+    pub fn new(file: &str) -> Self {
+        Self {
+            min: 1,
+            max: 1,
+            file: file.to_string(),
+            variable_name: READ_VARIABLE_NAME.to_string(),
+            timeout: Duration::from_secs(5),
+            terminators: "#".to_string(),
+        }
+    }
+    /// Minimum number of digits to collect
+    pub fn min(mut self, min: u8) -> Self {
+        self.min = min;
+        self
+    }
+    /// Maximum number of digits to collect
+    pub fn max(mut self, max: u8) -> Self {
+        self.max = max;
+        self
+    }
+    /// Channel variable where collected digits are stored, cleared before collecting
+    pub fn variable_name(mut self, variable_name: &str) -> Self {
+        self.variable_name = variable_name.to_string();
+        self
+    }
+    /// Time to wait for input after prompt is played
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    /// Digits which end input e.g. `#`
+    pub fn terminators(mut self, terminators: &str) -> Self {
+        self.terminators = terminators.to_string();
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.min,
+            self.max,
+            self.file,
+            self.variable_name,
+            self.timeout.as_millis(),
+            self.terminators
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Status of digit collection
+pub enum DigitsStatus {
+    /// Valid digits were collected
+    Success,
+    /// Collected digits didn't match the expected input
+    Invalid,
+    /// No digits were entered before timeout
+    Timeout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Outcome of `play_and_get_digits` or `read`
+pub struct DigitsResult {
+    status: DigitsStatus,
+    digits: Option<String>,
+    invalid_digits: Option<String>,
+    terminator: Option<String>,
+    result: ExecuteResult,
+}
+
+impl DigitsResult {
+    /// Returns status of digit collection
+    pub fn status(&self) -> DigitsStatus {
+        self.status
+    }
+    /// Returns collected digits when input was valid
+    pub fn digits(&self) -> Option<&str> {
+        self.digits.as_deref()
+    }
+    /// Returns last input which didn't match `digits_regex`
+    pub fn invalid_digits(&self) -> Option<&str> {
+        self.invalid_digits.as_deref()
+    }
+    /// Returns terminator which ended input
+    pub fn terminator(&self) -> Option<&str> {
+        self.terminator.as_deref()
+    }
+    /// Returns result of the application
+    pub fn execute_result(&self) -> &ExecuteResult {
+        &self.result
+    }
+
+    pub(crate) fn new(variable_name: &str, result: ExecuteResult) -> Self {
+        let non_empty = |name: &str| {
+            result
+                .variable(name)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let digits = non_empty(variable_name);
+        let invalid_digits = non_empty(&format!("{}_invalid", variable_name));
+        let terminator = non_empty("read_terminator_used");
+        let status = match (&digits, result.variable("read_result")) {
+            (_, Some("timeout")) => DigitsStatus::Timeout,
+            (Some(_), _) => DigitsStatus::Success,
+            (None, _) if invalid_digits.is_some() => DigitsStatus::Invalid,
+            (None, _) => DigitsStatus::Timeout,
+        };
+        Self {
+            status,
+            digits,
+            invalid_digits,
+            terminator,
+            result,
+        }
+    }
+}
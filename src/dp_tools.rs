@@ -1,78 +1,259 @@
-use std::collections::HashMap;
-
-use serde_json::Value;
-
 const PLAY_AND_GET_DIGITS_APP: &str = "play_and_get_digits";
 const PLAYBACK_APP: &str = "playback";
+const READ_APP: &str = "read";
+const RECORD_APP: &str = "record";
+const RECORD_SESSION_APP: &str = "record_session";
+const PLAYBACK_TERMINATORS_VARIABLE: &str = "playback_terminators";
 
-use crate::{EslConnection, EslError, Event};
+use crate::channel::{multiset_args, validate_variable_name, variable_assignment};
+use crate::digits::{DigitsResult, PlayAndGetDigits, ReadDigits};
+use crate::playback::{Playback, PlaybackResult};
+use crate::record::{Record, RecordEvent, RecordEventKind, RecordResult, RecordSession};
+use crate::{EslConnection, EslError, ExecuteResult};
 
 impl EslConnection {
     /// plays file in call during outbound mode
-    pub async fn playback(&self, file_path: &str) -> Result<Event, EslError> {
+    pub async fn playback(&self, file_path: &str) -> Result<ExecuteResult, EslError> {
         self.execute(PLAYBACK_APP, file_path).await
     }
 
+    /// plays files with given options and reports whether playback was interrupted
+    ///
+    /// PLAYBACK_STOP event has to be subscribed in inbound mode to report its status.
+    pub async fn playback_with(&self, options: &Playback) -> Result<PlaybackResult, EslError> {
+        let previous = self.set_terminators(&options.terminators).await?;
+        let mut events = self.call_events().await?;
+        let result = self.execute(PLAYBACK_APP, &options.app_args()).await;
+        let restored = self.restore_terminators(previous).await;
+        let result = result?;
+        restored?;
+        let mut playback_stop = None;
+        while let Ok(event) = events.try_recv() {
+            let event_name = event
+                .json_headers()
+                .and_then(|headers| headers.get("Event-Name")?.as_str().map(ToString::to_string));
+            if event_name.as_deref() == Some("PLAYBACK_STOP") {
+                playback_stop = Some(event);
+            }
+        }
+        Ok(PlaybackResult::new(result, playback_stop))
+    }
+
+    /// starts playback with given options without waiting for it to complete
+    ///
+    /// Returns the `Application-UUID` of the CHANNEL_EXECUTE_COMPLETE event fired
+    /// once playback completes, digits can be collected meanwhile with [`EslConnection::dtmf_events`].
+    pub async fn playback_async(&self, options: &Playback) -> Result<String, EslError> {
+        let previous = self.set_terminators(&options.terminators).await?;
+        let application_uuid = self
+            .execute_async(PLAYBACK_APP, &options.app_args())
+            .await?;
+        if let Some(previous) = previous {
+            // applications run in order, so terminators are restored once playback is done
+            let (app_name, app_args) = terminators_restore(previous)?;
+            self.execute_async(app_name, &app_args).await?;
+        }
+        Ok(application_uuid)
+    }
+
     /// record_session during outbound mode
-    pub async fn record_session(&self, file_path: &str) -> Result<Event, EslError> {
-        self.execute("record_session", file_path).await
+    pub async fn record_session(&self, file_path: &str) -> Result<ExecuteResult, EslError> {
+        self.execute(RECORD_SESSION_APP, file_path).await
+    }
+
+    /// starts recording call in background with given options
+    ///
+    /// RECORD_STOP is fired once recording stops, it can be parsed into [`RecordEvent`]
+    /// from [`EslConnection::call_events`].
+    pub async fn record_session_with(
+        &self,
+        options: &RecordSession,
+    ) -> Result<ExecuteResult, EslError> {
+        self.set_variables(&options.variables.to_vec()).await?;
+        self.execute(RECORD_SESSION_APP, &options.app_args()).await
+    }
+
+    /// stops recording started with `record_session`, `all` stops every recording
+    pub async fn stop_record_session(&self, file_path: &str) -> Result<ExecuteResult, EslError> {
+        self.execute("stop_record_session", file_path).await
+    }
+
+    /// records call until time limit, silence, terminator or hangup
+    pub async fn record(&self, options: &Record) -> Result<RecordResult, EslError> {
+        self.set_variables(&options.variables.to_vec()).await?;
+        let previous = self.set_terminators(&options.terminators).await?;
+        let mut events = self.call_events().await?;
+        let result = self.execute(RECORD_APP, &options.app_args()).await;
+        let restored = self.restore_terminators(previous).await;
+        let result = result?;
+        restored?;
+        let mut record_stop = None;
+        while let Ok(event) = events.try_recv() {
+            if let Ok(event) = RecordEvent::try_from(event) {
+                if event.kind() == RecordEventKind::Stop {
+                    record_stop = Some(event);
+                }
+            }
+        }
+        Ok(RecordResult::new(&options.path, result, record_stop))
+    }
+
+    /// sets `playback_terminators` for a single application
+    ///
+    /// Returns previous value to restore afterwards, `None` when nothing was set.
+    async fn set_terminators(
+        &self,
+        terminators: &Option<String>,
+    ) -> Result<Option<Option<String>>, EslError> {
+        let Some(terminators) = terminators else {
+            return Ok(None);
+        };
+        let previous = self.get_variable(PLAYBACK_TERMINATORS_VARIABLE).await?;
+        self.set_variable(PLAYBACK_TERMINATORS_VARIABLE, terminators)
+            .await?;
+        Ok(Some(previous))
+    }
+
+    /// restores `playback_terminators` returned by `set_terminators`
+    async fn restore_terminators(&self, previous: Option<Option<String>>) -> Result<(), EslError> {
+        if let Some(previous) = previous {
+            let (app_name, app_args) = terminators_restore(previous)?;
+            self.execute(app_name, &app_args).await?;
+        }
+        Ok(())
+    }
+
+    /// sets variables with a single `multiset`, skipped when there are none
+    pub(crate) async fn set_variables(&self, variables: &[(&str, String)]) -> Result<(), EslError> {
+        if variables.is_empty() {
+            return Ok(());
+        }
+        let variables: Vec<(&str, &str)> = variables
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+        self.multiset(&variables).await?;
+        Ok(())
+    }
+
+    /// returns current values of variables about to be set, restored with `restore_variables`
+    pub(crate) async fn previous_variables(
+        &self,
+        variables: &[(&'static str, String)],
+    ) -> Result<Vec<(&'static str, Option<String>)>, EslError> {
+        let mut previous = Vec::with_capacity(variables.len());
+        for (name, _) in variables {
+            previous.push((*name, self.get_variable(name).await?));
+        }
+        Ok(previous)
+    }
+
+    /// restores variables returned by `previous_variables`, unsetting ones which weren't set
+    pub(crate) async fn restore_variables(
+        &self,
+        previous: Vec<(&'static str, Option<String>)>,
+    ) -> Result<(), EslError> {
+        let mut set = Vec::new();
+        for (name, value) in previous {
+            match value {
+                Some(value) => set.push((name, value)),
+                None => {
+                    self.unset(name).await?;
+                }
+            }
+        }
+        self.set_variables(&set).await
     }
 
     /// send dtmf during outbound mode
-    pub async fn send_dtmf(&self, dtmf_str: &str) -> Result<Event, EslError> {
+    pub async fn send_dtmf(&self, dtmf_str: &str) -> Result<ExecuteResult, EslError> {
         self.execute("send_dtmf", dtmf_str).await
     }
 
     /// wait for silence during outbound mode
-    pub async fn wait_for_silence(&self, silence_str: &str) -> Result<Event, EslError> {
+    pub async fn wait_for_silence(&self, silence_str: &str) -> Result<ExecuteResult, EslError> {
         self.execute("wait_for_silence", silence_str).await
     }
 
     /// sleep for specified milliseconds in outbound mode
-    pub async fn sleep(&self, millis: u32) -> Result<Event, EslError> {
+    pub async fn sleep(&self, millis: u32) -> Result<ExecuteResult, EslError> {
         self.execute("sleep", &millis.to_string()).await
     }
 
     ///set a channel variable
-    pub async fn set_variable(&self, var: &str, value: &str) -> Result<Event, EslError> {
-        let args = format!("{}={}", var, value);
+    pub async fn set_variable(&self, var: &str, value: &str) -> Result<ExecuteResult, EslError> {
+        let args = variable_assignment(var, value)?;
         self.execute("set", &args).await
     }
 
+    /// set a channel variable and export it to the b-leg
+    pub async fn export(&self, var: &str, value: &str) -> Result<ExecuteResult, EslError> {
+        let args = variable_assignment(var, value)?;
+        self.execute("export", &args).await
+    }
+
+    /// set multiple channel variables at once
+    pub async fn multiset(&self, variables: &[(&str, &str)]) -> Result<ExecuteResult, EslError> {
+        let args = multiset_args(variables)?;
+        self.execute("multiset", &args).await
+    }
+
+    /// unset a channel variable
+    pub async fn unset(&self, var: &str) -> Result<ExecuteResult, EslError> {
+        validate_variable_name(var)?;
+        self.execute("unset", var).await
+    }
+
+    /// append value to array channel variable
+    pub async fn push(&self, var: &str, value: &str) -> Result<ExecuteResult, EslError> {
+        let args = variable_assignment(var, value)?;
+        self.execute("push", &args).await
+    }
+
+    /// prepend value to array channel variable
+    pub async fn unshift(&self, var: &str, value: &str) -> Result<ExecuteResult, EslError> {
+        let args = variable_assignment(var, value)?;
+        self.execute("unshift", &args).await
+    }
+
     ///add  a freeswitch log
-    pub async fn fs_log(&self, loglevel: &str, msg: &str) -> Result<Event, EslError> {
+    pub async fn fs_log(&self, loglevel: &str, msg: &str) -> Result<ExecuteResult, EslError> {
         let args = format!("{} {}", loglevel, msg);
         self.execute("log", &args).await
     }
 
-    #[allow(clippy::too_many_arguments)]
     /// Used for mod_play_and_get_digits
     pub async fn play_and_get_digits(
         &self,
-        min: u8,
-        max: u8,
-        tries: u8,
-        timeout: u64,
-        terminators: &str,
-        file: &str,
-        invalid_file: &str,
-    ) -> Result<String, EslError> {
-        let variable_name = uuid::Uuid::new_v4().to_string();
-        let app_args = format!(
-            "{min} {max} {tries} {timeout} {terminators} {file} {invalid_file} {variable_name}",
-        );
-        let data = self.execute(PLAY_AND_GET_DIGITS_APP, &app_args).await?;
-        let body = data.body.as_ref().unwrap();
-        let body = parse_json_body(body).unwrap();
-        let result = body.get(&format!("variable_{}", variable_name));
-        let Some(digit) = result else {
-            return Err(EslError::NoInput);
-        };
-        let digit = digit.as_str().unwrap().to_string();
-        Ok(digit)
+        options: &PlayAndGetDigits,
+    ) -> Result<DigitsResult, EslError> {
+        validate_variable_name(&options.variable_name)?;
+        // digits of an earlier collection would be reported when this one fails
+        self.unset(&options.variable_name).await?;
+        self.unset(&format!("{}_invalid", options.variable_name))
+            .await?;
+        let result = self
+            .execute(PLAY_AND_GET_DIGITS_APP, &options.app_args())
+            .await?;
+        Ok(DigitsResult::new(&options.variable_name, result))
+    }
+
+    /// collects digits using `read` application
+    pub async fn read(&self, options: &ReadDigits) -> Result<DigitsResult, EslError> {
+        validate_variable_name(&options.variable_name)?;
+        self.unset(&options.variable_name).await?;
+        let result = self.execute(READ_APP, &options.app_args()).await?;
+        Ok(DigitsResult::new(&options.variable_name, result))
     }
 }
 
-fn parse_json_body(body: &str) -> Result<HashMap<String, Value>, EslError> {
-    Ok(serde_json::from_str(body)?)
+/// Returns application restoring previous `playback_terminators`, unset when it had no value
+fn terminators_restore(previous: Option<String>) -> Result<(&'static str, String), EslError> {
+    match previous {
+        Some(previous) => Ok((
+            "set",
+            variable_assignment(PLAYBACK_TERMINATORS_VARIABLE, &previous)?,
+        )),
+        None => Ok(("unset", PLAYBACK_TERMINATORS_VARIABLE.to_string())),
+    }
 }
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::event::Event;
+use crate::{EslConnection, EslError, HangupCause};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// DTMF digit pressed on call, parsed from DTMF event
+pub struct Dtmf {
+    digit: char,
+    duration: Option<u32>,
+    source: Option<String>,
+}
+
+impl Dtmf {
+    /// Returns pressed digit
+    pub fn digit(&self) -> char {
+        self.digit
+    }
+    /// Returns `DTMF-Duration` in samples
+    pub fn duration(&self) -> Option<u32> {
+        self.duration
+    }
+    /// Returns `DTMF-Source` e.g. `RTP` or `INBAND_AUDIO`
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    fn from_headers(headers: &HashMap<String, Value>) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.as_str();
+        let digit = header("DTMF-Digit")?.chars().next()?;
+        Some(Self {
+            digit,
+            duration: header("DTMF-Duration").and_then(|duration| duration.parse().ok()),
+            source: header("DTMF-Source").map(ToString::to_string),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for [`EslConnection::collect_digits`]
+///
+/// ```rust
+/// use std::time::Duration;
+/// use freeswitch_esl::CollectDigits;
+///
+/// let options = CollectDigits::new(4)
+///     .terminators("#")
+///     .first_digit_timeout(Duration::from_secs(10))
+///     .inter_digit_timeout(Duration::from_secs(3));
+/// ```
+pub struct CollectDigits {
+    pub(crate) max_digits: usize,
+    pub(crate) terminators: String,
+    pub(crate) first_digit_timeout: Option<Duration>,
+    pub(crate) inter_digit_timeout: Duration,
+}
+
+impl CollectDigits {
+    /// Collects up to `max_digits` digits waiting 5 seconds for each digit
+    pub fn new(max_digits: usize) -> Self {
+        Self {
+            max_digits,
+            terminators: String::new(),
+            first_digit_timeout: None,
+            inter_digit_timeout: Duration::from_secs(5),
+        }
+    }
+    /// Digits which end input, terminator is not included in collected digits
+    pub fn terminators(mut self, terminators: &str) -> Self {
+        self.terminators = terminators.to_string();
+        self
+    }
+    /// Time to wait for first digit, defaults to `inter_digit_timeout`
+    pub fn first_digit_timeout(mut self, timeout: Duration) -> Self {
+        self.first_digit_timeout = Some(timeout);
+        self
+    }
+    /// Time to wait for each following digit
+    pub fn inter_digit_timeout(mut self, timeout: Duration) -> Self {
+        self.inter_digit_timeout = timeout;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Reason why digit collection ended
+pub enum CollectStatus {
+    /// Maximum number of digits was collected
+    MaxDigits,
+    /// Terminator was pressed
+    Terminator,
+    /// No digit was pressed before timeout
+    Timeout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Digits collected with [`DtmfStream::collect`]
+pub struct CollectedDigits {
+    status: CollectStatus,
+    digits: String,
+    terminator: Option<char>,
+}
+
+impl CollectedDigits {
+    /// Returns why collection ended
+    pub fn status(&self) -> CollectStatus {
+        self.status
+    }
+    /// Returns collected digits without terminator
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+    /// Returns terminator which ended input
+    pub fn terminator(&self) -> Option<char> {
+        self.terminator
+    }
+}
+
+#[derive(Debug)]
+/// Stream of DTMF pressed on call, created with [`EslConnection::dtmf_events`]
+///
+/// Digits are buffered from the moment the stream is created, so digits pressed
+/// while a prompt started with [`EslConnection::playback_async`] is playing are
+/// not lost.
+pub struct DtmfStream {
+    events: UnboundedReceiver<Event>,
+    hangup_cause: Option<HangupCause>,
+}
+
+impl DtmfStream {
+    /// Receives next digit, returns `None` once call is hung up or connection is closed
+    pub async fn recv(&mut self) -> Option<Dtmf> {
+        self.next_dtmf().await.ok()
+    }
+
+    /// Collects digits until `max_digits`, terminator or timeout
+    ///
+    /// Fails with [`EslError::ChannelHungUp`] when call is hung up while collecting.
+    pub async fn collect(&mut self, options: &CollectDigits) -> Result<CollectedDigits, EslError> {
+        let mut digits = String::new();
+        let mut timeout = options
+            .first_digit_timeout
+            .unwrap_or(options.inter_digit_timeout);
+        let (status, terminator) = loop {
+            if digits.len() >= options.max_digits {
+                break (CollectStatus::MaxDigits, None);
+            }
+            let Ok(dtmf) = tokio::time::timeout(timeout, self.next_dtmf()).await else {
+                break (CollectStatus::Timeout, None);
+            };
+            let digit = dtmf?.digit;
+            if options.terminators.contains(digit) {
+                break (CollectStatus::Terminator, Some(digit));
+            }
+            digits.push(digit);
+            timeout = options.inter_digit_timeout;
+        };
+        Ok(CollectedDigits {
+            status,
+            digits,
+            terminator,
+        })
+    }
+
+    async fn next_dtmf(&mut self) -> Result<Dtmf, EslError> {
+        if let Some(hangup_cause) = &self.hangup_cause {
+            return Err(EslError::ChannelHungUp(hangup_cause.clone()));
+        }
+        while let Some(event) = self.events.recv().await {
+            let Some(headers) = event.json_headers() else {
+                continue;
+            };
+            match headers.get("Event-Name").and_then(Value::as_str) {
+                Some("DTMF") => {
+                    if let Some(dtmf) = Dtmf::from_headers(&headers) {
+                        return Ok(dtmf);
+                    }
+                }
+                Some("CHANNEL_HANGUP") => {
+                    let hangup_cause = headers
+                        .get("Hangup-Cause")
+                        .and_then(Value::as_str)
+                        .map(HangupCause::from)
+                        .unwrap_or(HangupCause::None);
+                    self.hangup_cause = Some(hangup_cause.clone());
+                    return Err(EslError::ChannelHungUp(hangup_cause));
+                }
+                _ => {}
+            }
+        }
+        Err(EslError::ConnectionError(
+            "connection closed while waiting for DTMF".into(),
+        ))
+    }
+}
+
+impl EslConnection {
+    /// Returns stream of DTMF pressed on the call
+    ///
+    /// DTMF events are subscribed in outbound mode, on inbound connections
+    /// `DTMF` and `CHANNEL_HANGUP` have to be subscribed with [`EslConnection::subscribe`].
+    pub async fn dtmf_events(&self) -> Result<DtmfStream, EslError> {
+        Ok(DtmfStream {
+            events: self.call_events().await?,
+            hangup_cause: None,
+        })
+    }
+
+    /// Collects digits pressed on the call from DTMF events
+    ///
+    /// Only digits pressed after this is called are collected, use
+    /// [`EslConnection::dtmf_events`] and [`DtmfStream::collect`] to collect digits
+    /// pressed while a prompt is playing.
+    pub async fn collect_digits(
+        &self,
+        options: &CollectDigits,
+    ) -> Result<CollectedDigits, EslError> {
+        self.dtmf_events().await?.collect(options).await
+    }
+}
@@ -2,6 +2,8 @@ use std::num::ParseIntError;
 
 use thiserror::Error;
 
+use crate::HangupCause;
+
 #[derive(Clone, Debug, PartialEq, Ord, PartialOrd, Eq, Hash, Error)]
 #[allow(missing_docs)]
 /// Error type for Esl
@@ -23,6 +25,24 @@ pub enum EslError {
 
     #[error("Didnt get any digits")]
     NoInput,
+
+    #[error("Call uuid is not available on this connection")]
+    NoCallUuid,
+
+    #[error("Channel hung up with cause {0}")]
+    ChannelHungUp(HangupCause),
+
+    #[error("Invalid channel variable name {0:?}")]
+    InvalidVariable(String),
+
+    #[error("No such channel {0}")]
+    NoSuchChannel(String),
+
+    #[error("Originate failed with cause {0}")]
+    OriginateFailed(HangupCause),
+
+    #[error("Invalid event header {0:?}")]
+    InvalidHeader(String),
 }
 
 impl From<std::io::Error> for EslError {
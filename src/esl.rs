@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use tokio::net::TcpStream;
 
 use crate::{connection::EslConnection, EslError};
@@ -6,6 +8,41 @@ pub(crate) enum EslConnectionType {
     Inbound,
     Outbound,
 }
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Options applied while accepting outbound connection with [`Esl::outbound_with`]
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use freeswitch_esl::OutboundOptions;
+///
+/// let options = OutboundOptions::new().linger_timeout(Duration::from_secs(10));
+/// ```
+pub struct OutboundOptions {
+    pub(crate) linger: bool,
+    pub(crate) linger_timeout: Option<Duration>,
+}
+
+impl OutboundOptions {
+    /// Creates options which close socket as soon as call is hung up
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `linger` so events after hangup like CHANNEL_HANGUP_COMPLETE are received
+    pub fn linger(mut self, linger: bool) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    /// Enables linger closing socket after given timeout, rounded up to whole seconds
+    pub fn linger_timeout(mut self, timeout: Duration) -> Self {
+        self.linger = true;
+        self.linger_timeout = Some(timeout);
+        self
+    }
+}
+
 /// Esl struct with inbound and outbound method.
 pub struct Esl;
 impl Esl {
@@ -19,6 +56,18 @@ impl Esl {
 
     /// Creates new server for outbound connection
     pub async fn outbound(stream: TcpStream) -> Result<EslConnection, EslError> {
-        EslConnection::new(stream, "None", EslConnectionType::Outbound).await
+        Self::outbound_with(stream, &OutboundOptions::default()).await
+    }
+
+    /// Creates new server for outbound connection with given options
+    pub async fn outbound_with(
+        stream: TcpStream,
+        options: &OutboundOptions,
+    ) -> Result<EslConnection, EslError> {
+        let connection = EslConnection::new(stream, "None", EslConnectionType::Outbound).await?;
+        if options.linger {
+            connection.linger(options.linger_timeout).await?;
+        }
+        Ok(connection)
     }
 }
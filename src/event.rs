@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde_json::Value;
 
+use crate::EslError;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Structure of event returned from freeswitch
 pub struct Event {
@@ -17,4 +19,12 @@ impl Event {
     pub fn body(&self) -> &Option<String> {
         &self.body
     }
+    /// Returns headers of event-json event which are sent in body
+    pub(crate) fn json_headers(&self) -> Option<HashMap<String, Value>> {
+        parse_json_body(self.body.as_ref()?).ok()
+    }
+}
+
+pub(crate) fn parse_json_body(body: &str) -> Result<HashMap<String, Value>, EslError> {
+    Ok(serde_json::from_str(body)?)
 }
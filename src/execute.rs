@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::event::{parse_json_body, Event};
+use crate::EslError;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Additional headers sent with `sendmsg` while executing application
+pub struct ExecuteOptions {
+    pub(crate) loops: Option<u32>,
+    pub(crate) event_lock: bool,
+}
+
+impl ExecuteOptions {
+    /// Creates options which executes application once without event lock
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of times application should be executed using `loops` header
+    ///
+    /// [`EslConnection::execute_with`](crate::EslConnection::execute_with) waits for
+    /// all iterations to complete.
+    pub fn loops(mut self, loops: u32) -> Self {
+        self.loops = Some(loops);
+        self
+    }
+
+    /// Sets `event-lock` header so applications are executed in the order they were sent
+    pub fn event_lock(mut self, event_lock: bool) -> Self {
+        self.event_lock = event_lock;
+        self
+    }
+
+    pub(crate) fn sendmsg(
+        &self,
+        call_uuid: &str,
+        app_name: &str,
+        app_args: &str,
+        event_uuid: &str,
+    ) -> String {
+        let mut command = format!(
+            "sendmsg {}\nexecute-app-name: {}\nexecute-app-arg: {}\n",
+            call_uuid, app_name, app_args
+        );
+        if let Some(loops) = self.loops {
+            command.push_str(&format!("loops: {}\n", loops));
+        }
+        if self.event_lock {
+            command.push_str("event-lock: true\n");
+        }
+        command.push_str(&format!(
+            "call-command: execute\nEvent-UUID: {}",
+            event_uuid
+        ));
+        command
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Result of application built from CHANNEL_EXECUTE_COMPLETE event
+pub struct ExecuteResult {
+    application: String,
+    application_data: String,
+    response: Option<String>,
+    hungup: bool,
+    headers: HashMap<String, Value>,
+    event: Event,
+}
+
+impl ExecuteResult {
+    /// Returns name of executed application
+    pub fn application(&self) -> &str {
+        &self.application
+    }
+    /// Returns arguments application was executed with
+    pub fn application_data(&self) -> &str {
+        &self.application_data
+    }
+    /// Returns `Application-Response` or `variable_current_application_response`
+    pub fn response(&self) -> Option<&str> {
+        self.response.as_deref()
+    }
+    /// Returns true if channel was hung up when application completed
+    pub fn hungup(&self) -> bool {
+        self.hungup
+    }
+    /// Returns channel variable from completion event without `variable_` prefix
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.header(&format!("variable_{}", name))
+    }
+    /// Returns header from completion event
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.as_str()
+    }
+    /// Returns CHANNEL_EXECUTE_COMPLETE event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl TryFrom<Event> for ExecuteResult {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let body = event.body().as_ref().ok_or_else(|| {
+            EslError::InternalError("body was not found in CHANNEL_EXECUTE_COMPLETE".into())
+        })?;
+        let headers = parse_json_body(body)?;
+        let header = |name: &str| headers.get(name).and_then(|value| value.as_str());
+        let response = header("Application-Response")
+            .filter(|response| *response != "_none_")
+            .or_else(|| header("variable_current_application_response"))
+            .map(ToString::to_string);
+        let hungup = header("Answer-State") == Some("hangup")
+            || matches!(
+                header("Channel-State"),
+                Some("CS_HANGUP" | "CS_REPORTING" | "CS_DESTROY")
+            );
+        Ok(Self {
+            application: header("Application").unwrap_or_default().to_string(),
+            application_data: header("Application-Data").unwrap_or_default().to_string(),
+            response,
+            hungup,
+            headers,
+            event,
+        })
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(missing_docs)]
+/// Hangup cause of channel as reported in `Hangup-Cause` header
+pub enum HangupCause {
+    None,
+    UnallocatedNumber,
+    NoRouteTransitNet,
+    NoRouteDestination,
+    ChannelUnacceptable,
+    CallAwardedDelivered,
+    NormalClearing,
+    UserBusy,
+    NoUserResponse,
+    NoAnswer,
+    SubscriberAbsent,
+    CallRejected,
+    NumberChanged,
+    RedirectionToNewDestination,
+    ExchangeRoutingError,
+    DestinationOutOfOrder,
+    InvalidNumberFormat,
+    FacilityRejected,
+    ResponseToStatusEnquiry,
+    NormalUnspecified,
+    NormalCircuitCongestion,
+    NetworkOutOfOrder,
+    NormalTemporaryFailure,
+    SwitchCongestion,
+    AccessInfoDiscarded,
+    RequestedChanUnavail,
+    PreEmpted,
+    FacilityNotSubscribed,
+    OutgoingCallBarred,
+    IncomingCallBarred,
+    BearercapabilityNotauth,
+    BearercapabilityNotavail,
+    ServiceUnavailable,
+    BearercapabilityNotimpl,
+    ChanNotImplemented,
+    FacilityNotImplemented,
+    ServiceNotImplemented,
+    InvalidCallReference,
+    IncompatibleDestination,
+    InvalidMsgUnspecified,
+    MandatoryIeMissing,
+    MessageTypeNonexist,
+    WrongMessage,
+    IeNonexist,
+    InvalidIeContents,
+    WrongCallState,
+    RecoveryOnTimerExpire,
+    MandatoryIeLengthError,
+    ProtocolError,
+    Interworking,
+    OriginatorCancel,
+    Crash,
+    SystemShutdown,
+    LoseRace,
+    ManagerRequest,
+    BlindTransfer,
+    AttendedTransfer,
+    AllottedTimeout,
+    UserChallenge,
+    MediaTimeout,
+    PickedOff,
+    UserNotRegistered,
+    ProgressTimeout,
+    GatewayDown,
+    /// Cause which is not known to this crate
+    Other(String),
+}
+
+impl HangupCause {
+    /// Returns cause as used by freeswitch e.g. `NORMAL_CLEARING`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::None => "NONE",
+            Self::UnallocatedNumber => "UNALLOCATED_NUMBER",
+            Self::NoRouteTransitNet => "NO_ROUTE_TRANSIT_NET",
+            Self::NoRouteDestination => "NO_ROUTE_DESTINATION",
+            Self::ChannelUnacceptable => "CHANNEL_UNACCEPTABLE",
+            Self::CallAwardedDelivered => "CALL_AWARDED_DELIVERED",
+            Self::NormalClearing => "NORMAL_CLEARING",
+            Self::UserBusy => "USER_BUSY",
+            Self::NoUserResponse => "NO_USER_RESPONSE",
+            Self::NoAnswer => "NO_ANSWER",
+            Self::SubscriberAbsent => "SUBSCRIBER_ABSENT",
+            Self::CallRejected => "CALL_REJECTED",
+            Self::NumberChanged => "NUMBER_CHANGED",
+            Self::RedirectionToNewDestination => "REDIRECTION_TO_NEW_DESTINATION",
+            Self::ExchangeRoutingError => "EXCHANGE_ROUTING_ERROR",
+            Self::DestinationOutOfOrder => "DESTINATION_OUT_OF_ORDER",
+            Self::InvalidNumberFormat => "INVALID_NUMBER_FORMAT",
+            Self::FacilityRejected => "FACILITY_REJECTED",
+            Self::ResponseToStatusEnquiry => "RESPONSE_TO_STATUS_ENQUIRY",
+            Self::NormalUnspecified => "NORMAL_UNSPECIFIED",
+            Self::NormalCircuitCongestion => "NORMAL_CIRCUIT_CONGESTION",
+            Self::NetworkOutOfOrder => "NETWORK_OUT_OF_ORDER",
+            Self::NormalTemporaryFailure => "NORMAL_TEMPORARY_FAILURE",
+            Self::SwitchCongestion => "SWITCH_CONGESTION",
+            Self::AccessInfoDiscarded => "ACCESS_INFO_DISCARDED",
+            Self::RequestedChanUnavail => "REQUESTED_CHAN_UNAVAIL",
+            Self::PreEmpted => "PRE_EMPTED",
+            Self::FacilityNotSubscribed => "FACILITY_NOT_SUBSCRIBED",
+            Self::OutgoingCallBarred => "OUTGOING_CALL_BARRED",
+            Self::IncomingCallBarred => "INCOMING_CALL_BARRED",
+            Self::BearercapabilityNotauth => "BEARERCAPABILITY_NOTAUTH",
+            Self::BearercapabilityNotavail => "BEARERCAPABILITY_NOTAVAIL",
+            Self::ServiceUnavailable => "SERVICE_UNAVAILABLE",
+            Self::BearercapabilityNotimpl => "BEARERCAPABILITY_NOTIMPL",
+            Self::ChanNotImplemented => "CHAN_NOT_IMPLEMENTED",
+            Self::FacilityNotImplemented => "FACILITY_NOT_IMPLEMENTED",
+            Self::ServiceNotImplemented => "SERVICE_NOT_IMPLEMENTED",
+            Self::InvalidCallReference => "INVALID_CALL_REFERENCE",
+            Self::IncompatibleDestination => "INCOMPATIBLE_DESTINATION",
+            Self::InvalidMsgUnspecified => "INVALID_MSG_UNSPECIFIED",
+            Self::MandatoryIeMissing => "MANDATORY_IE_MISSING",
+            Self::MessageTypeNonexist => "MESSAGE_TYPE_NONEXIST",
+            Self::WrongMessage => "WRONG_MESSAGE",
+            Self::IeNonexist => "IE_NONEXIST",
+            Self::InvalidIeContents => "INVALID_IE_CONTENTS",
+            Self::WrongCallState => "WRONG_CALL_STATE",
+            Self::RecoveryOnTimerExpire => "RECOVERY_ON_TIMER_EXPIRE",
+            Self::MandatoryIeLengthError => "MANDATORY_IE_LENGTH_ERROR",
+            Self::ProtocolError => "PROTOCOL_ERROR",
+            Self::Interworking => "INTERWORKING",
+            Self::OriginatorCancel => "ORIGINATOR_CANCEL",
+            Self::Crash => "CRASH",
+            Self::SystemShutdown => "SYSTEM_SHUTDOWN",
+            Self::LoseRace => "LOSE_RACE",
+            Self::ManagerRequest => "MANAGER_REQUEST",
+            Self::BlindTransfer => "BLIND_TRANSFER",
+            Self::AttendedTransfer => "ATTENDED_TRANSFER",
+            Self::AllottedTimeout => "ALLOTTED_TIMEOUT",
+            Self::UserChallenge => "USER_CHALLENGE",
+            Self::MediaTimeout => "MEDIA_TIMEOUT",
+            Self::PickedOff => "PICKED_OFF",
+            Self::UserNotRegistered => "USER_NOT_REGISTERED",
+            Self::ProgressTimeout => "PROGRESS_TIMEOUT",
+            Self::GatewayDown => "GATEWAY_DOWN",
+            Self::Other(cause) => cause,
+        }
+    }
+}
+
+impl From<&str> for HangupCause {
+    fn from(cause: &str) -> Self {
+        match cause {
+            "NONE" => Self::None,
+            "UNALLOCATED_NUMBER" => Self::UnallocatedNumber,
+            "NO_ROUTE_TRANSIT_NET" => Self::NoRouteTransitNet,
+            "NO_ROUTE_DESTINATION" => Self::NoRouteDestination,
+            "CHANNEL_UNACCEPTABLE" => Self::ChannelUnacceptable,
+            "CALL_AWARDED_DELIVERED" => Self::CallAwardedDelivered,
+            "NORMAL_CLEARING" => Self::NormalClearing,
+            "USER_BUSY" => Self::UserBusy,
+            "NO_USER_RESPONSE" => Self::NoUserResponse,
+            "NO_ANSWER" => Self::NoAnswer,
+            "SUBSCRIBER_ABSENT" => Self::SubscriberAbsent,
+            "CALL_REJECTED" => Self::CallRejected,
+            "NUMBER_CHANGED" => Self::NumberChanged,
+            "REDIRECTION_TO_NEW_DESTINATION" => Self::RedirectionToNewDestination,
+            "EXCHANGE_ROUTING_ERROR" => Self::ExchangeRoutingError,
+            "DESTINATION_OUT_OF_ORDER" => Self::DestinationOutOfOrder,
+            "INVALID_NUMBER_FORMAT" => Self::InvalidNumberFormat,
+            "FACILITY_REJECTED" => Self::FacilityRejected,
+            "RESPONSE_TO_STATUS_ENQUIRY" => Self::ResponseToStatusEnquiry,
+            "NORMAL_UNSPECIFIED" => Self::NormalUnspecified,
+            "NORMAL_CIRCUIT_CONGESTION" => Self::NormalCircuitCongestion,
+            "NETWORK_OUT_OF_ORDER" => Self::NetworkOutOfOrder,
+            "NORMAL_TEMPORARY_FAILURE" => Self::NormalTemporaryFailure,
+            "SWITCH_CONGESTION" => Self::SwitchCongestion,
+            "ACCESS_INFO_DISCARDED" => Self::AccessInfoDiscarded,
+            "REQUESTED_CHAN_UNAVAIL" => Self::RequestedChanUnavail,
+            "PRE_EMPTED" => Self::PreEmpted,
+            "FACILITY_NOT_SUBSCRIBED" => Self::FacilityNotSubscribed,
+            "OUTGOING_CALL_BARRED" => Self::OutgoingCallBarred,
+            "INCOMING_CALL_BARRED" => Self::IncomingCallBarred,
+            "BEARERCAPABILITY_NOTAUTH" => Self::BearercapabilityNotauth,
+            "BEARERCAPABILITY_NOTAVAIL" => Self::BearercapabilityNotavail,
+            "SERVICE_UNAVAILABLE" => Self::ServiceUnavailable,
+            "BEARERCAPABILITY_NOTIMPL" => Self::BearercapabilityNotimpl,
+            "CHAN_NOT_IMPLEMENTED" => Self::ChanNotImplemented,
+            "FACILITY_NOT_IMPLEMENTED" => Self::FacilityNotImplemented,
+            "SERVICE_NOT_IMPLEMENTED" => Self::ServiceNotImplemented,
+            "INVALID_CALL_REFERENCE" => Self::InvalidCallReference,
+            "INCOMPATIBLE_DESTINATION" => Self::IncompatibleDestination,
+            "INVALID_MSG_UNSPECIFIED" => Self::InvalidMsgUnspecified,
+            "MANDATORY_IE_MISSING" => Self::MandatoryIeMissing,
+            "MESSAGE_TYPE_NONEXIST" => Self::MessageTypeNonexist,
+            "WRONG_MESSAGE" => Self::WrongMessage,
+            "IE_NONEXIST" => Self::IeNonexist,
+            "INVALID_IE_CONTENTS" => Self::InvalidIeContents,
+            "WRONG_CALL_STATE" => Self::WrongCallState,
+            "RECOVERY_ON_TIMER_EXPIRE" => Self::RecoveryOnTimerExpire,
+            "MANDATORY_IE_LENGTH_ERROR" => Self::MandatoryIeLengthError,
+            "PROTOCOL_ERROR" => Self::ProtocolError,
+            "INTERWORKING" => Self::Interworking,
+            "ORIGINATOR_CANCEL" => Self::OriginatorCancel,
+            "CRASH" => Self::Crash,
+            "SYSTEM_SHUTDOWN" => Self::SystemShutdown,
+            "LOSE_RACE" => Self::LoseRace,
+            "MANAGER_REQUEST" => Self::ManagerRequest,
+            "BLIND_TRANSFER" => Self::BlindTransfer,
+            "ATTENDED_TRANSFER" => Self::AttendedTransfer,
+            "ALLOTTED_TIMEOUT" => Self::AllottedTimeout,
+            "USER_CHALLENGE" => Self::UserChallenge,
+            "MEDIA_TIMEOUT" => Self::MediaTimeout,
+            "PICKED_OFF" => Self::PickedOff,
+            "USER_NOT_REGISTERED" => Self::UserNotRegistered,
+            "PROGRESS_TIMEOUT" => Self::ProgressTimeout,
+            "GATEWAY_DOWN" => Self::GatewayDown,
+            cause => Self::Other(cause.to_string()),
+        }
+    }
+}
+
+impl Display for HangupCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
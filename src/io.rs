@@ -19,6 +19,27 @@ impl Encoder<&[u8]> for EslCodec {
     }
 }
 
+/// Command followed by body which is sent with `Content-Length`
+pub(crate) struct CommandWithBody<'a> {
+    pub(crate) command: &'a [u8],
+    pub(crate) body: &'a [u8],
+}
+
+impl Encoder<CommandWithBody<'_>> for EslCodec {
+    type Error = EslError;
+    fn encode(
+        &mut self,
+        item: CommandWithBody<'_>,
+        dst: &mut bytes::BytesMut,
+    ) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.command);
+        dst.extend_from_slice(format!("\nContent-Length: {}\n\n", item.body.len()).as_bytes());
+        // body is framed by its length, no blank line follows it
+        dst.extend_from_slice(item.body);
+        Ok(())
+    }
+}
+
 fn get_header_end(src: &bytes::BytesMut) -> Option<usize> {
     trace!("get_header_end:=>{:?}", src);
     // get first new line character
@@ -34,18 +55,38 @@ fn parse_body(src: &[u8], length: usize) -> String {
     trace!("length src : {}", length);
     String::from_utf8_lossy(&src[..length]).to_string()
 }
+fn url_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
 fn parse_header(src: &[u8]) -> Result<HashMap<String, Value>, std::io::Error> {
     trace!("parsing this header {:#?}", String::from_utf8_lossy(src));
     let data = String::from_utf8_lossy(src).to_string();
     let a = data.split('\n');
     let mut hash = HashMap::new();
     for line in a {
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() == 2 {
-            // SAFETY: Index access is safe beacue we have checked the length
-            let key = parts[0].trim();
-            let val = parts[1].trim();
-            hash.insert(key.to_string(), serde_json::json!(val.to_string()));
+        if let Some((key, val)) = line.split_once(':') {
+            let key = key.trim();
+            let val = val.trim();
+            hash.insert(key.to_string(), serde_json::json!(val));
         } else {
             warn!("Invalid formatting while parsing header");
         }
@@ -54,6 +95,24 @@ fn parse_header(src: &[u8]) -> Result<HashMap<String, Value>, std::io::Error> {
     Ok(hash)
 }
 
+/// Channel headers of `connect` reply and plain events are url encoded, other replies
+/// are kept as they are
+fn decode_channel_headers(headers: &mut HashMap<String, Value>) {
+    let encoded = match headers.get("Content-Type").and_then(Value::as_str) {
+        Some("text/event-plain") => true,
+        Some("command/reply") => headers.contains_key("Channel-Unique-ID"),
+        _ => false,
+    };
+    if !encoded {
+        return;
+    }
+    for value in headers.values_mut() {
+        if let Some(decoded) = value.as_str().map(url_decode) {
+            *value = Value::String(decoded);
+        }
+    }
+}
+
 impl Decoder for EslCodec {
     type Item = Event;
     type Error = EslError;
@@ -64,7 +123,8 @@ impl Decoder for EslCodec {
             Some(he) => he,
             None => return Ok(None),
         };
-        let headers = parse_header(&src[..(header_end - 1)])?;
+        let mut headers = parse_header(&src[..(header_end - 1)])?;
+        decode_channel_headers(&mut headers);
         trace!("parsed headers are : {:?}", headers);
         let body_start = header_end + 1;
         let Some(length) = headers.get("Content-Length") else {
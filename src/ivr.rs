@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::digits::{DigitsStatus, ReadDigits};
+use crate::{EslConnection, EslError, HangupCause};
+
+const IVR_VARIABLE_NAME: &str = "ivr_menu_digits";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Action performed when caller selects an entry of [`IvrMenu`]
+pub enum IvrAction {
+    /// Enters sub menu, [`IvrAction::Back`] returns to the current menu
+    SubMenu(IvrMenu),
+    /// Plays file and repeats the current menu
+    Playback(String),
+    /// Transfers call using `transfer` application e.g. `1000 XML default`
+    Transfer(String),
+    /// Hangs up call with given cause
+    Hangup(HangupCause),
+    /// Returns to parent menu
+    Back,
+    /// Plays greeting of the current menu again
+    Repeat,
+    /// Leaves IVR returning given value to the caller of [`EslConnection::run_ivr`]
+    Return(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How IVR ended
+pub enum IvrOutcome {
+    /// Caller selected [`IvrAction::Return`]
+    Returned {
+        /// Name of menu where entry was selected
+        menu: String,
+        /// Value of the selected action
+        value: String,
+    },
+    /// Call was transferred to given destination
+    Transferred(String),
+    /// Call was hung up by [`IvrAction::Hangup`]
+    HungUp(HangupCause),
+    /// Caller failed to make valid selection `max_failures` times in given menu
+    MaxFailures(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Declarative IVR menu executed with [`EslConnection::run_ivr`]
+///
+/// ```rust
+/// use freeswitch_esl::{HangupCause, IvrAction, IvrMenu};
+///
+/// let sales = IvrMenu::new("sales", "ivr/ivr-sales.wav")
+///     .action("1", IvrAction::Transfer("2000 XML default".into()))
+///     .action("*", IvrAction::Back);
+/// let main = IvrMenu::new("main", "ivr/ivr-welcome.wav")
+///     .invalid_prompt("ivr/ivr-that_was_an_invalid_entry.wav")
+///     .action("1", IvrAction::SubMenu(sales))
+///     .action("9", IvrAction::Hangup(HangupCause::NormalClearing));
+/// ```
+pub struct IvrMenu {
+    name: String,
+    greeting: String,
+    invalid_prompt: Option<String>,
+    timeout_prompt: Option<String>,
+    exit_prompt: Option<String>,
+    max_failures: u8,
+    max_digits: u8,
+    timeout: Duration,
+    actions: HashMap<String, IvrAction>,
+}
+
+impl IvrMenu {
+    /// Creates menu playing greeting, allowing 3 failures with 5 second timeout
+    pub fn new(name: &str, greeting: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            greeting: greeting.to_string(),
+            invalid_prompt: None,
+            timeout_prompt: None,
+            exit_prompt: None,
+            max_failures: 3,
+            max_digits: 1,
+            timeout: Duration::from_secs(5),
+            actions: HashMap::new(),
+        }
+    }
+    /// Maps entered digits to action
+    pub fn action(mut self, digits: &str, action: IvrAction) -> Self {
+        self.max_digits = self.max_digits.max(digits.len() as u8);
+        self.actions.insert(digits.to_string(), action);
+        self
+    }
+    /// File played when entered digits don't match any action
+    pub fn invalid_prompt(mut self, invalid_prompt: &str) -> Self {
+        self.invalid_prompt = Some(invalid_prompt.to_string());
+        self
+    }
+    /// File played when no digits were entered, defaults to invalid prompt
+    pub fn timeout_prompt(mut self, timeout_prompt: &str) -> Self {
+        self.timeout_prompt = Some(timeout_prompt.to_string());
+        self
+    }
+    /// File played before leaving menu after `max_failures`
+    pub fn exit_prompt(mut self, exit_prompt: &str) -> Self {
+        self.exit_prompt = Some(exit_prompt.to_string());
+        self
+    }
+    /// Number of invalid or missing entries before leaving menu
+    pub fn max_failures(mut self, max_failures: u8) -> Self {
+        self.max_failures = max_failures;
+        self
+    }
+    /// Time to wait for entry after greeting is played
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    /// Returns name of menu
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn read_digits(&self) -> ReadDigits {
+        ReadDigits::new(&self.greeting)
+            .max(self.max_digits)
+            .variable_name(IVR_VARIABLE_NAME)
+            .timeout(self.timeout)
+    }
+}
+
+impl EslConnection {
+    /// Runs IVR menu on the call until an action leaves it
+    ///
+    /// Menus only execute `read`, `playback`, `transfer` and `hangup`, so they can be
+    /// tested against a simulated channel.
+    pub async fn run_ivr(&self, menu: &IvrMenu) -> Result<IvrOutcome, EslError> {
+        let mut menus = vec![menu];
+        let mut failures = 0;
+        loop {
+            let menu = menus[menus.len() - 1];
+            let digits = self.read(&menu.read_digits()).await?;
+            let action = match (digits.status(), digits.digits()) {
+                (DigitsStatus::Success, Some(digits)) => menu.actions.get(digits),
+                _ => None,
+            };
+            let Some(action) = action else {
+                failures += 1;
+                let prompt = match digits.status() {
+                    DigitsStatus::Timeout => menu.timeout_prompt.as_ref(),
+                    _ => None,
+                };
+                if failures >= menu.max_failures {
+                    if let Some(exit_prompt) = &menu.exit_prompt {
+                        self.playback(exit_prompt).await?;
+                    }
+                    return Ok(IvrOutcome::MaxFailures(menu.name.clone()));
+                }
+                if let Some(prompt) = prompt.or(menu.invalid_prompt.as_ref()) {
+                    self.playback(prompt).await?;
+                }
+                continue;
+            };
+            failures = 0;
+            match action {
+                IvrAction::SubMenu(sub_menu) => menus.push(sub_menu),
+                IvrAction::Playback(file) => {
+                    self.playback(file).await?;
+                }
+                IvrAction::Transfer(destination) => {
+                    self.execute("transfer", destination).await?;
+                    return Ok(IvrOutcome::Transferred(destination.clone()));
+                }
+                IvrAction::Hangup(hangup_cause) => {
+                    self.hangup(hangup_cause.as_str()).await?;
+                    return Ok(IvrOutcome::HungUp(hangup_cause.clone()));
+                }
+                IvrAction::Back => {
+                    if menus.len() > 1 {
+                        menus.pop();
+                    }
+                }
+                IvrAction::Repeat => {}
+                IvrAction::Return(value) => {
+                    return Ok(IvrOutcome::Returned {
+                        menu: menu.name.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
@@ -29,24 +29,23 @@
 //! ## Outbound Connection
 //!
 //!```rust,no_run
-//! use freeswitch_esl::{Esl, EslConnection, EslError};
+//! use std::time::Duration;
+//!
+//! use freeswitch_esl::{Esl, EslConnection, EslError, PlayAndGetDigits};
 //! use tokio::net::TcpListener;
 //!
 //! async fn process_call(conn: EslConnection) -> Result<(), EslError> {
 //!     conn.answer().await?;
 //!     println!("answered call");
 //!     conn.playback("ivr/ivr-welcome.wav").await?;
-//!     let digit = conn
+//!     let digits = conn
 //!         .play_and_get_digits(
-//!             1,
-//!             1,
-//!             3,
-//!             3000,
-//!             "#",
-//!             "conference/conf-pin.wav",
-//!             "conference/conf-bad-pin.wav",
+//!             &PlayAndGetDigits::new("conference/conf-pin.wav")
+//!                 .invalid_file("conference/conf-bad-pin.wav")
+//!                 .timeout(Duration::from_secs(3)),
 //!         )
 //!         .await?;
+//!     let digit = digits.digits().ok_or(EslError::NoInput)?;
 //!     println!("got digit {}", digit);
 //!     conn.playback("ivr/ivr-you_entered.wav").await?;
 //!     conn.playback(&format!("digits/{}.wav", digit)).await?;
@@ -71,15 +70,53 @@
 //! }
 //! ```
 
+pub(crate) mod api_tools;
+pub(crate) mod bridge;
+pub(crate) mod callcenter;
+pub(crate) mod channel;
 pub(crate) mod code;
+pub(crate) mod conference;
 pub(crate) mod connection;
+pub(crate) mod digits;
 pub(crate) mod dp_tools;
+pub(crate) mod dtmf;
 pub(crate) mod error;
 pub(crate) mod esl;
 pub(crate) mod event;
+pub(crate) mod execute;
+pub(crate) mod hangup;
 pub(crate) mod io;
+pub(crate) mod ivr;
+pub(crate) mod playback;
+pub(crate) mod presence;
+pub(crate) mod record;
+pub(crate) mod registration;
+pub(crate) mod roster;
+pub(crate) mod say;
+pub(crate) mod sendevent;
+pub(crate) mod sofia;
+pub(crate) mod transfer;
 
+pub use api_tools::HoldAction;
+pub use bridge::*;
+pub use callcenter::*;
+pub use channel::*;
+pub use conference::*;
 pub use connection::EslConnection;
+pub use digits::*;
+pub use dtmf::*;
 pub use error::*;
 pub use esl::*;
 pub use event::*;
+pub use execute::*;
+pub use hangup::*;
+pub use ivr::*;
+pub use playback::*;
+pub use presence::*;
+pub use record::*;
+pub use registration::*;
+pub use roster::*;
+pub use say::*;
+pub use sendevent::*;
+pub use sofia::*;
+pub use transfer::*;
@@ -0,0 +1,147 @@
+use crate::event::Event;
+use crate::ExecuteResult;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `playback` application
+///
+/// ```rust
+/// use freeswitch_esl::Playback;
+///
+/// let options = Playback::new("ivr/ivr-welcome.wav")
+///     .file("ivr/ivr-please_hold.wav")
+///     .terminators("#*")
+///     .volume(2);
+/// ```
+pub struct Playback {
+    pub(crate) files: Vec<String>,
+    pub(crate) terminators: Option<String>,
+    pub(crate) start_offset: Option<u64>,
+    pub(crate) volume: Option<i8>,
+}
+
+impl Playback {
+    /// Plays single file
+    pub fn new(file: &str) -> Self {
+        Self {
+            files: vec![file.to_string()],
+            terminators: None,
+            start_offset: None,
+            volume: None,
+        }
+    }
+    /// Appends file which is played after previous files using `file_string://`
+    pub fn file(mut self, file: &str) -> Self {
+        self.files.push(file.to_string());
+        self
+    }
+    /// Digits which interrupt playback, set as `playback_terminators`
+    pub fn terminators(mut self, terminators: &str) -> Self {
+        self.terminators = Some(terminators.to_string());
+        self
+    }
+    /// Starts playback of first file from given sample offset
+    pub fn start_offset(mut self, samples: u64) -> Self {
+        self.start_offset = Some(samples);
+        self
+    }
+    /// Adjusts volume of playback from -4 to 4
+    pub fn volume(mut self, volume: i8) -> Self {
+        self.volume = Some(volume.clamp(-4, 4));
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        let mut files = self.files.clone();
+        if let (Some(start_offset), Some(file)) = (self.start_offset, files.first_mut()) {
+            file.push_str(&format!("@@{}", start_offset));
+        }
+        let file = match files.len() {
+            1 => files.remove(0),
+            _ => format!("file_string://{}", files.join("!")),
+        };
+        match self.volume {
+            Some(volume) => format!("{{volume={}}}{}", volume, file),
+            None => file,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Status of completed playback
+pub enum PlaybackStatus {
+    /// Every file was played completely
+    Done,
+    /// Playback was interrupted by DTMF or `uuid_break`
+    Break,
+    /// File was not found
+    NotFound,
+    /// Playback failed
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Outcome of `playback` application
+pub struct PlaybackResult {
+    status: PlaybackStatus,
+    terminator_used: Option<String>,
+    playback_ms: Option<u64>,
+    playback_stop: Option<Event>,
+    result: ExecuteResult,
+}
+
+impl PlaybackResult {
+    /// Returns whether playback finished or was interrupted
+    pub fn status(&self) -> PlaybackStatus {
+        self.status
+    }
+    /// Returns `playback_terminator_used` when playback was interrupted by DTMF
+    pub fn terminator_used(&self) -> Option<&str> {
+        self.terminator_used.as_deref()
+    }
+    /// Returns `playback_ms`, time spent playing the files
+    pub fn playback_ms(&self) -> Option<u64> {
+        self.playback_ms
+    }
+    /// Returns PLAYBACK_STOP event when it was received
+    pub fn playback_stop(&self) -> Option<&Event> {
+        self.playback_stop.as_ref()
+    }
+    /// Returns result of the application
+    pub fn execute_result(&self) -> &ExecuteResult {
+        &self.result
+    }
+
+    /// Builds result from completion event and PLAYBACK_STOP fired while playing
+    pub(crate) fn new(result: ExecuteResult, playback_stop: Option<Event>) -> Self {
+        let terminator_used = result
+            .variable("playback_terminator_used")
+            .filter(|terminator| !terminator.is_empty())
+            .map(ToString::to_string);
+        let playback_ms = result
+            .variable("playback_ms")
+            .and_then(|playback_ms| playback_ms.parse().ok());
+        let playback_status = playback_stop
+            .as_ref()
+            .and_then(Event::json_headers)
+            .and_then(|body| {
+                body.get("Playback-Status")?
+                    .as_str()
+                    .map(ToString::to_string)
+            });
+        let status = match (playback_status.as_deref(), result.response()) {
+            (_, Some("FILE NOT FOUND")) => PlaybackStatus::NotFound,
+            (_, Some("PLAYBACK ERROR")) => PlaybackStatus::Error,
+            (Some("break"), _) => PlaybackStatus::Break,
+            (Some(_), _) => PlaybackStatus::Done,
+            (None, _) if terminator_used.is_some() => PlaybackStatus::Break,
+            (None, _) => PlaybackStatus::Done,
+        };
+        Self {
+            status,
+            terminator_used,
+            playback_ms,
+            playback_stop,
+            result,
+        }
+    }
+}
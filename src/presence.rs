@@ -0,0 +1,359 @@
+use std::fmt::Display;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{EslConnection, EslError, Event, EventBuilder};
+
+const PRESENCE_IN: &str = "PRESENCE_IN";
+const PRESENCE_OUT: &str = "PRESENCE_OUT";
+const PRESENCE_PROBE: &str = "PRESENCE_PROBE";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// State of call reported in `answer-state`, drives BLF lamps
+pub enum AnswerState {
+    /// Call is ringing, BLF lamp blinks
+    Early,
+    /// Call is answered, BLF lamp is lit
+    Confirmed,
+    /// Call ended, BLF lamp is off
+    Terminated,
+    /// Any other state
+    Other(String),
+}
+
+impl AnswerState {
+    /// Returns state as sent in `answer-state` header
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Early => "early",
+            Self::Confirmed => "confirmed",
+            Self::Terminated => "terminated",
+            Self::Other(state) => state,
+        }
+    }
+
+    /// Returns `channel-state` matching answer state
+    fn channel_state(&self) -> &str {
+        match self {
+            Self::Terminated => "CS_HANGUP",
+            _ => "CS_ROUTING",
+        }
+    }
+}
+
+impl From<&str> for AnswerState {
+    fn from(state: &str) -> Self {
+        match state {
+            "early" => Self::Early,
+            "confirmed" => Self::Confirmed,
+            "terminated" => Self::Terminated,
+            state => Self::Other(state.to_string()),
+        }
+    }
+}
+
+impl Display for AnswerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `PRESENCE_IN` event published with [`EslConnection::send_presence`]
+///
+/// ```rust
+/// use freeswitch_esl::{AnswerState, Presence};
+///
+/// let presence = Presence::new("1000@example.com")
+///     .status("On the phone")
+///     .answer_state(AnswerState::Confirmed);
+/// ```
+pub struct Presence {
+    pub(crate) from: String,
+    pub(crate) proto: String,
+    pub(crate) login: Option<String>,
+    pub(crate) status: String,
+    pub(crate) rpid: String,
+    pub(crate) event_type: String,
+    pub(crate) answer_state: Option<AnswerState>,
+    pub(crate) unique_id: Option<String>,
+    pub(crate) direction: Option<String>,
+}
+
+impl Presence {
+    /// Publishes presence of given user e.g. `1000@example.com` over `sip`
+    pub fn new(from: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            proto: "sip".to_string(),
+            login: None,
+            status: "Available".to_string(),
+            rpid: "unknown".to_string(),
+            event_type: "presence".to_string(),
+            answer_state: None,
+            unique_id: None,
+            direction: None,
+        }
+    }
+    /// Presence protocol, defaults to `sip`
+    pub fn proto(mut self, proto: &str) -> Self {
+        self.proto = proto.to_string();
+        self
+    }
+    /// Login of presence, defaults to `from`
+    pub fn login(mut self, login: &str) -> Self {
+        self.login = Some(login.to_string());
+        self
+    }
+    /// Status text shown on phones, defaults to `Available`
+    pub fn status(mut self, status: &str) -> Self {
+        self.status = status.to_string();
+        self
+    }
+    /// Rich presence e.g. `away` or `busy`, defaults to `unknown`
+    pub fn rpid(mut self, rpid: &str) -> Self {
+        self.rpid = rpid.to_string();
+        self
+    }
+    /// Event package, defaults to `presence`
+    pub fn event_type(mut self, event_type: &str) -> Self {
+        self.event_type = event_type.to_string();
+        self
+    }
+    /// Call state sent as dialog info, required to light BLF lamps
+    pub fn answer_state(mut self, answer_state: AnswerState) -> Self {
+        self.answer_state = Some(answer_state);
+        self
+    }
+    /// Id of call presence is about, generated when answer state is set
+    pub fn unique_id(mut self, unique_id: &str) -> Self {
+        self.unique_id = Some(unique_id.to_string());
+        self
+    }
+    /// Direction of call e.g. `inbound` or `outbound`, defaults to `outbound`
+    pub fn direction(mut self, direction: &str) -> Self {
+        self.direction = Some(direction.to_string());
+        self
+    }
+
+    /// Returns `PRESENCE_IN` event fired by [`EslConnection::send_presence`]
+    pub fn event(&self) -> EventBuilder {
+        let event = EventBuilder::new(PRESENCE_IN)
+            .header("proto", &self.proto)
+            .header("from", &self.from)
+            .header("login", self.login.as_deref().unwrap_or(&self.from))
+            .header("status", &self.status)
+            .header("rpid", &self.rpid)
+            .header("event_type", &self.event_type);
+        let Some(answer_state) = &self.answer_state else {
+            return event;
+        };
+        let unique_id = self
+            .unique_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        event
+            .header("alt_event_type", "dialog")
+            .header("event_count", "1")
+            .header("unique-id", &unique_id)
+            .header("channel-state", answer_state.channel_state())
+            .header("answer-state", answer_state.as_str())
+            .header(
+                "presence-call-direction",
+                self.direction.as_deref().unwrap_or("outbound"),
+            )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `PRESENCE_PROBE` event asking for presence of a user
+pub struct PresenceProbe {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) proto: String,
+}
+
+impl PresenceProbe {
+    /// Asks on behalf of `from` for presence of `to`, both e.g. `1000@example.com`
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            proto: "sip".to_string(),
+        }
+    }
+    /// Presence protocol, defaults to `sip`
+    pub fn proto(mut self, proto: &str) -> Self {
+        self.proto = proto.to_string();
+        self
+    }
+
+    /// Returns `PRESENCE_PROBE` event fired by [`EslConnection::send_presence_probe`]
+    pub fn event(&self) -> EventBuilder {
+        EventBuilder::new(PRESENCE_PROBE)
+            .header("proto", &self.proto)
+            .header("login", &self.from)
+            .header("from", &self.from)
+            .header("to", &self.to)
+            .header("event_type", "presence")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Kind of presence event
+pub enum PresenceEventKind {
+    /// `PRESENCE_IN`, presence of user changed
+    In,
+    /// `PRESENCE_OUT`, presence is sent out to subscribers
+    Out,
+    /// `PRESENCE_PROBE`, presence of user is requested
+    Probe,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Typed `PRESENCE_IN`, `PRESENCE_OUT` or `PRESENCE_PROBE` event
+pub struct PresenceEvent {
+    kind: PresenceEventKind,
+    proto: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    login: Option<String>,
+    status: Option<String>,
+    rpid: Option<String>,
+    event_type: Option<String>,
+    answer_state: Option<AnswerState>,
+    unique_id: Option<String>,
+    direction: Option<String>,
+    event: Event,
+}
+
+impl PresenceEvent {
+    /// Returns kind of event
+    pub fn kind(&self) -> PresenceEventKind {
+        self.kind
+    }
+    /// Returns `proto` e.g. `sip`
+    pub fn proto(&self) -> Option<&str> {
+        self.proto.as_deref()
+    }
+    /// Returns `from`, user presence is about
+    pub fn from(&self) -> Option<&str> {
+        self.from.as_deref()
+    }
+    /// Returns `to`, user probe is sent to
+    pub fn to(&self) -> Option<&str> {
+        self.to.as_deref()
+    }
+    /// Returns `login`
+    pub fn login(&self) -> Option<&str> {
+        self.login.as_deref()
+    }
+    /// Returns `status` text
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+    /// Returns `rpid`, rich presence
+    pub fn rpid(&self) -> Option<&str> {
+        self.rpid.as_deref()
+    }
+    /// Returns `event_type` e.g. `presence`
+    pub fn event_type(&self) -> Option<&str> {
+        self.event_type.as_deref()
+    }
+    /// Returns `answer-state` of call presence is about
+    pub fn answer_state(&self) -> Option<&AnswerState> {
+        self.answer_state.as_ref()
+    }
+    /// Returns `unique-id` of call presence is about
+    pub fn unique_id(&self) -> Option<&str> {
+        self.unique_id.as_deref()
+    }
+    /// Returns `presence-call-direction`
+    pub fn direction(&self) -> Option<&str> {
+        self.direction.as_deref()
+    }
+    /// Returns raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl TryFrom<Event> for PresenceEvent {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let headers = event.json_headers().ok_or_else(|| {
+            EslError::InternalError("body was not found in presence event".into())
+        })?;
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+        };
+        let string = |name: &str| header(name).map(ToString::to_string);
+        let kind = match header("Event-Name") {
+            Some(PRESENCE_IN) => PresenceEventKind::In,
+            Some(PRESENCE_OUT) => PresenceEventKind::Out,
+            Some(PRESENCE_PROBE) => PresenceEventKind::Probe,
+            _ => {
+                return Err(EslError::InternalError(
+                    "event is not presence event".into(),
+                ))
+            }
+        };
+        Ok(Self {
+            kind,
+            proto: string("proto"),
+            from: string("from"),
+            to: string("to"),
+            login: string("login"),
+            status: string("status"),
+            rpid: string("rpid"),
+            event_type: string("event_type"),
+            answer_state: header("answer-state").map(AnswerState::from),
+            unique_id: string("unique-id").or_else(|| string("Unique-ID")),
+            direction: string("presence-call-direction"),
+            event,
+        })
+    }
+}
+
+#[derive(Debug)]
+/// Stream of presence events, created with [`EslConnection::presence_events`]
+pub struct PresenceEvents {
+    events: UnboundedReceiver<Event>,
+}
+
+impl PresenceEvents {
+    /// Receives next presence event, returns `None` once connection is closed
+    pub async fn recv(&mut self) -> Option<PresenceEvent> {
+        while let Some(event) = self.events.recv().await {
+            if let Ok(event) = PresenceEvent::try_from(event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+impl EslConnection {
+    /// returns stream of `PRESENCE_IN`, `PRESENCE_OUT` and `PRESENCE_PROBE` events
+    pub async fn presence_events(&self) -> Result<PresenceEvents, EslError> {
+        Ok(PresenceEvents {
+            events: self
+                .named_events(&[PRESENCE_IN, PRESENCE_OUT, PRESENCE_PROBE])
+                .await?,
+        })
+    }
+
+    /// publishes presence of user with `PRESENCE_IN` event, returns `Event-UUID` of the event
+    pub async fn send_presence(&self, presence: &Presence) -> Result<String, EslError> {
+        self.sendevent(&presence.event()).await
+    }
+
+    /// requests presence of user with `PRESENCE_PROBE` event, returns `Event-UUID` of the event
+    pub async fn send_presence_probe(&self, probe: &PresenceProbe) -> Result<String, EslError> {
+        self.sendevent(&probe.event()).await
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::connection::whole_seconds;
+use crate::event::Event;
+use crate::EslError;
+use crate::ExecuteResult;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `record` application
+///
+/// ```rust
+/// use std::time::Duration;
+/// use freeswitch_esl::Record;
+///
+/// let options = Record::new("/tmp/voicemail.wav")
+///     .time_limit(Duration::from_secs(60))
+///     .silence_threshold(200)
+///     .silence_hits(3)
+///     .terminators("#");
+/// ```
+pub struct Record {
+    pub(crate) path: String,
+    pub(crate) time_limit: Option<Duration>,
+    pub(crate) silence_threshold: Option<u32>,
+    pub(crate) silence_hits: Option<u32>,
+    pub(crate) terminators: Option<String>,
+    pub(crate) variables: RecordVariables,
+}
+
+impl Record {
+    /// Records call to given path until hangup or terminator
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            time_limit: None,
+            silence_threshold: None,
+            silence_hits: None,
+            terminators: None,
+            variables: RecordVariables::default(),
+        }
+    }
+    /// Maximum length of recording, rounded up to whole seconds
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+    /// Energy level below which audio is considered silence
+    pub fn silence_threshold(mut self, silence_threshold: u32) -> Self {
+        self.silence_threshold = Some(silence_threshold);
+        self
+    }
+    /// Seconds of silence after which recording stops
+    pub fn silence_hits(mut self, silence_hits: u32) -> Self {
+        self.silence_hits = Some(silence_hits);
+        self
+    }
+    /// Digits which stop recording, set as `playback_terminators`
+    pub fn terminators(mut self, terminators: &str) -> Self {
+        self.terminators = Some(terminators.to_string());
+        self
+    }
+    /// Records caller and callee in separate channels, set as `RECORD_STEREO`
+    pub fn stereo(mut self, stereo: bool) -> Self {
+        self.variables.stereo = Some(stereo);
+        self
+    }
+    /// Sample rate of recording, set as `record_sample_rate`
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.variables.sample_rate = Some(sample_rate);
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        let mut args = vec![self.path.clone()];
+        // arguments are positional, so later ones need the earlier slots filled
+        if self.time_limit.is_some()
+            || self.silence_threshold.is_some()
+            || self.silence_hits.is_some()
+        {
+            let time_limit = self.time_limit.map(whole_seconds).unwrap_or(0);
+            args.push(time_limit.to_string());
+        }
+        if self.silence_threshold.is_some() || self.silence_hits.is_some() {
+            args.push(self.silence_threshold.unwrap_or(0).to_string());
+        }
+        if let Some(silence_hits) = self.silence_hits {
+            args.push(silence_hits.to_string());
+        }
+        args.join(" ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `record_session` application
+///
+/// ```rust
+/// use std::time::Duration;
+/// use freeswitch_esl::RecordSession;
+///
+/// let options = RecordSession::new("/recordings/call.wav")
+///     .stereo(true)
+///     .sample_rate(16000)
+///     .time_limit(Duration::from_secs(3600));
+/// ```
+pub struct RecordSession {
+    pub(crate) path: String,
+    pub(crate) time_limit: Option<Duration>,
+    pub(crate) variables: RecordVariables,
+}
+
+impl RecordSession {
+    /// Records call to given path in background until hangup
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            time_limit: None,
+            variables: RecordVariables::default(),
+        }
+    }
+    /// Maximum length of recording, rounded up to whole seconds
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+    /// Records caller and callee in separate channels, set as `RECORD_STEREO`
+    pub fn stereo(mut self, stereo: bool) -> Self {
+        self.variables.stereo = Some(stereo);
+        self
+    }
+    /// Sample rate of recording, set as `record_sample_rate`
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.variables.sample_rate = Some(sample_rate);
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        match self.time_limit {
+            Some(time_limit) => format!("{} +{}", self.path, whole_seconds(time_limit)),
+            None => self.path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Channel variables which have to be set before recording starts
+pub(crate) struct RecordVariables {
+    stereo: Option<bool>,
+    sample_rate: Option<u32>,
+}
+
+impl RecordVariables {
+    pub(crate) fn to_vec(&self) -> Vec<(&'static str, String)> {
+        let mut variables = Vec::new();
+        if let Some(stereo) = self.stereo {
+            variables.push(("RECORD_STEREO", stereo.to_string()));
+        }
+        if let Some(sample_rate) = self.sample_rate {
+            variables.push(("record_sample_rate", sample_rate.to_string()));
+        }
+        variables
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Type of recording event
+pub enum RecordEventKind {
+    /// RECORD_START
+    Start,
+    /// RECORD_STOP
+    Stop,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Parsed RECORD_START or RECORD_STOP event
+pub struct RecordEvent {
+    kind: RecordEventKind,
+    unique_id: Option<String>,
+    path: String,
+    record_ms: Option<u64>,
+    record_seconds: Option<u64>,
+    completion_cause: Option<String>,
+    event: Event,
+}
+
+impl RecordEvent {
+    /// Returns whether recording started or stopped
+    pub fn kind(&self) -> RecordEventKind {
+        self.kind
+    }
+    /// Returns `Unique-ID` of recorded call
+    pub fn unique_id(&self) -> Option<&str> {
+        self.unique_id.as_deref()
+    }
+    /// Returns `Record-File-Path`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Returns `record_ms`, length of recording in milliseconds
+    pub fn record_ms(&self) -> Option<u64> {
+        self.record_ms
+    }
+    /// Returns length of recording from `record_ms` or `record_seconds`
+    pub fn duration(&self) -> Option<Duration> {
+        self.record_ms
+            .map(Duration::from_millis)
+            .or_else(|| self.record_seconds.map(Duration::from_secs))
+    }
+    /// Returns `Record-Completion-Cause` e.g. `success-maxtime`
+    pub fn completion_cause(&self) -> Option<&str> {
+        self.completion_cause.as_deref()
+    }
+    /// Returns raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    fn from_headers(headers: &HashMap<String, Value>, event: Event) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.as_str();
+        let kind = match header("Event-Name")? {
+            "RECORD_START" => RecordEventKind::Start,
+            "RECORD_STOP" => RecordEventKind::Stop,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            unique_id: header("Unique-ID").map(ToString::to_string),
+            path: header("Record-File-Path").unwrap_or_default().to_string(),
+            record_ms: header("variable_record_ms").and_then(|ms| ms.parse().ok()),
+            record_seconds: header("variable_record_seconds").and_then(|secs| secs.parse().ok()),
+            completion_cause: header("Record-Completion-Cause").map(ToString::to_string),
+            event,
+        })
+    }
+}
+
+impl TryFrom<Event> for RecordEvent {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let headers = event
+            .json_headers()
+            .ok_or_else(|| EslError::InternalError("body was not found in record event".into()))?;
+        Self::from_headers(&headers, event).ok_or_else(|| {
+            EslError::InternalError("event is not RECORD_START or RECORD_STOP".into())
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Outcome of `record` application
+pub struct RecordResult {
+    path: String,
+    record_ms: Option<u64>,
+    terminator_used: Option<String>,
+    record_stop: Option<RecordEvent>,
+    result: ExecuteResult,
+}
+
+impl RecordResult {
+    /// Returns path of recorded file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Returns `record_ms`, length of recording in milliseconds
+    pub fn record_ms(&self) -> Option<u64> {
+        self.record_ms
+    }
+    /// Returns length of recording
+    pub fn duration(&self) -> Option<Duration> {
+        self.record_ms.map(Duration::from_millis)
+    }
+    /// Returns `playback_terminator_used` when recording was stopped by DTMF
+    pub fn terminator_used(&self) -> Option<&str> {
+        self.terminator_used.as_deref()
+    }
+    /// Returns RECORD_STOP event when it was received
+    pub fn record_stop(&self) -> Option<&RecordEvent> {
+        self.record_stop.as_ref()
+    }
+    /// Returns result of the application
+    pub fn execute_result(&self) -> &ExecuteResult {
+        &self.result
+    }
+
+    /// Builds result from completion event and RECORD_STOP fired while recording
+    pub(crate) fn new(path: &str, result: ExecuteResult, record_stop: Option<RecordEvent>) -> Self {
+        let record_ms = result
+            .variable("record_ms")
+            .and_then(|record_ms| record_ms.parse().ok())
+            .or_else(|| record_stop.as_ref()?.record_ms());
+        let terminator_used = result
+            .variable("playback_terminator_used")
+            .filter(|terminator| !terminator.is_empty())
+            .map(ToString::to_string);
+        Self {
+            path: path.to_string(),
+            record_ms,
+            terminator_used,
+            record_stop,
+            result,
+        }
+    }
+}
@@ -0,0 +1,535 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::MissedTickBehavior;
+
+use crate::{EslConnection, EslError, Event};
+
+const REGISTER_SUBCLASS: &str = "sofia::register";
+const UNREGISTER_SUBCLASS: &str = "sofia::unregister";
+const EXPIRE_SUBCLASS: &str = "sofia::expire";
+const CHANGES_CAPACITY: usize = 256;
+/// How often updater checks for expired registrations, independent of incoming events
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// SIP registration of an endpoint
+pub struct Registration {
+    user: String,
+    realm: String,
+    contact: String,
+    profile: Option<String>,
+    call_id: Option<String>,
+    network_ip: Option<String>,
+    network_port: Option<u16>,
+    user_agent: Option<String>,
+    expires_at: SystemTime,
+}
+
+impl Registration {
+    /// Returns registered user e.g. `1000`
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+    /// Returns domain user registered to e.g. `example.com`
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+    /// Returns address of record e.g. `1000@example.com`
+    pub fn aor(&self) -> String {
+        format!("{}@{}", self.user, self.realm)
+    }
+    /// Returns SIP uri endpoint is reached at, without parameters
+    pub fn contact(&self) -> &str {
+        &self.contact
+    }
+    /// Returns sofia profile which received registration
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+    /// Returns `Call-ID` of REGISTER requests
+    pub fn call_id(&self) -> Option<&str> {
+        self.call_id.as_deref()
+    }
+    /// Returns IP address registration was received from
+    pub fn network_ip(&self) -> Option<&str> {
+        self.network_ip.as_deref()
+    }
+    /// Returns port registration was received from
+    pub fn network_port(&self) -> Option<u16> {
+        self.network_port
+    }
+    /// Returns `User-Agent` of endpoint
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+    /// Returns when registration expires
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+    /// Returns time left until registration expires, zero once expired
+    pub fn expires_in(&self) -> Duration {
+        self.expires_at
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+    /// Returns true if registration has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+
+    /// Parses row of `show registrations as json`
+    fn from_row(row: &Value) -> Option<Self> {
+        let string = |name: &str| {
+            row.get(name)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let url = string("url")?;
+        let profile = url
+            .strip_prefix("sofia/")
+            .and_then(|url| url.split_once('/'))
+            .map(|(profile, _)| profile.to_string());
+        let expires = string("expires").and_then(|expires| expires.parse().ok());
+        Some(Self {
+            user: string("reg_user")?,
+            realm: string("realm")?,
+            contact: contact_uri(&url),
+            profile,
+            call_id: string("token"),
+            network_ip: string("network_ip"),
+            network_port: string("network_port").and_then(|port| port.parse().ok()),
+            user_agent: None,
+            expires_at: UNIX_EPOCH + Duration::from_secs(expires.unwrap_or_default()),
+        })
+    }
+
+    /// Parses block of `sofia status profile <name> reg`
+    fn from_block(profile: &str, block: &str) -> Option<Self> {
+        let fields: HashMap<&str, &str> = block
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+        let string = |name: &str| {
+            fields
+                .get(name)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let (user, realm) = fields.get("User")?.split_once('@')?;
+        // Status: Registered(UDP)(unknown) EXP(2023-09-12 05:31:37) EXPSECS(3590)
+        let expires = fields
+            .get("Status")
+            .and_then(|status| status.split("EXPSECS(").nth(1))
+            .and_then(|expires| expires.trim_end_matches(')').parse().ok())
+            .unwrap_or_default();
+        Some(Self {
+            user: user.to_string(),
+            realm: realm.to_string(),
+            contact: contact_uri(fields.get("Contact")?),
+            profile: Some(profile.to_string()),
+            call_id: string("Call-ID"),
+            network_ip: string("IP"),
+            network_port: string("Port").and_then(|port| port.parse().ok()),
+            user_agent: string("Agent"),
+            expires_at: SystemTime::now() + Duration::from_secs(expires),
+        })
+    }
+}
+
+/// Extracts SIP uri from `"name" <uri;params>` or `sofia/<profile>/<uri>`
+fn contact_uri(contact: &str) -> String {
+    let uri = match (contact.find('<'), contact.find('>')) {
+        (Some(start), Some(end)) if start < end => &contact[start + 1..end],
+        _ => match contact.strip_prefix("sofia/") {
+            Some(url) => url.split_once('/').map_or(url, |(_, uri)| uri),
+            None => contact,
+        },
+    };
+    uri.split(';').next().unwrap_or_default().trim().to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Kind of registration event
+pub enum RegistrationEventKind {
+    /// `sofia::register`, endpoint registered or refreshed registration
+    Register,
+    /// `sofia::unregister`, endpoint unregistered
+    Unregister,
+    /// `sofia::expire`, registration expired
+    Expire,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Typed `sofia::register`, `sofia::unregister` or `sofia::expire` event
+pub struct RegistrationEvent {
+    kind: RegistrationEventKind,
+    registration: Registration,
+    status: Option<String>,
+    event: Event,
+}
+
+impl RegistrationEvent {
+    /// Returns kind of event
+    pub fn kind(&self) -> RegistrationEventKind {
+        self.kind
+    }
+    /// Returns registration event is about
+    pub fn registration(&self) -> &Registration {
+        &self.registration
+    }
+    /// Returns `status` of registration e.g. `Registered(UDP)`
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+    /// Returns raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl TryFrom<Event> for RegistrationEvent {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let headers = event.json_headers().ok_or_else(|| {
+            EslError::InternalError("body was not found in registration event".into())
+        })?;
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+        };
+        let string = |name: &str| header(name).map(ToString::to_string);
+        let kind = match header("Event-Subclass") {
+            Some(REGISTER_SUBCLASS) => RegistrationEventKind::Register,
+            Some(UNREGISTER_SUBCLASS) => RegistrationEventKind::Unregister,
+            Some(EXPIRE_SUBCLASS) => RegistrationEventKind::Expire,
+            _ => {
+                return Err(EslError::InternalError(
+                    "event is not sofia registration event".into(),
+                ))
+            }
+        };
+        let missing = |name: &str| EslError::InternalError(format!("{} was not found", name));
+        let expires = header("expires")
+            .and_then(|expires| expires.parse().ok())
+            .unwrap_or_default();
+        // sofia::expire carries `user` and `host` instead of `from-user` and `from-host`
+        let registration = Registration {
+            user: string("from-user")
+                .or_else(|| string("user"))
+                .ok_or_else(|| missing("from-user"))?,
+            realm: string("from-host")
+                .or_else(|| string("host"))
+                .ok_or_else(|| missing("from-host"))?,
+            contact: header("contact").map(contact_uri).unwrap_or_default(),
+            profile: string("profile-name"),
+            call_id: string("call-id"),
+            network_ip: string("network-ip"),
+            network_port: header("network-port").and_then(|port| port.parse().ok()),
+            user_agent: string("user-agent"),
+            expires_at: SystemTime::now() + Duration::from_secs(expires),
+        };
+        Ok(Self {
+            kind,
+            registration,
+            status: string("status"),
+            event,
+        })
+    }
+}
+
+#[derive(Debug)]
+/// Stream of registration events, created with [`EslConnection::registration_events`]
+pub struct RegistrationEvents {
+    events: UnboundedReceiver<Event>,
+}
+
+impl RegistrationEvents {
+    /// Receives next registration event, returns `None` once connection is closed
+    pub async fn recv(&mut self) -> Option<RegistrationEvent> {
+        while let Some(event) = self.events.recv().await {
+            if let Ok(event) = RegistrationEvent::try_from(event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Change of [`RegistrationRegistry`]
+pub enum RegistrationChange {
+    /// Endpoint registered, or refreshed its registration
+    Registered(Registration),
+    /// Endpoint unregistered
+    Unregistered(Registration),
+    /// Registration expired without being refreshed
+    Expired(Registration),
+}
+
+impl RegistrationChange {
+    /// Returns registration which changed
+    pub fn registration(&self) -> &Registration {
+        match self {
+            Self::Registered(registration)
+            | Self::Unregistered(registration)
+            | Self::Expired(registration) => registration,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Registrations of every endpoint kept current from sofia registration events
+///
+/// Created with [`EslConnection::registration_registry`], clones share the same registry.
+pub struct RegistrationRegistry {
+    registrations: Arc<RwLock<HashMap<String, Vec<Registration>>>>,
+    changes: broadcast::Sender<RegistrationChange>,
+}
+
+impl Default for RegistrationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegistrationRegistry {
+    /// Creates empty registry which is updated with [`RegistrationRegistry::apply`]
+    pub fn new() -> Self {
+        let (changes, _) = broadcast::channel(CHANGES_CAPACITY);
+        Self {
+            registrations: Arc::new(RwLock::new(HashMap::new())),
+            changes,
+        }
+    }
+
+    /// Returns snapshot of every registration
+    pub fn registrations(&self) -> Vec<Registration> {
+        self.read(|registrations| registrations.values().flatten().cloned().collect())
+    }
+
+    /// Returns registrations of address of record e.g. `1000@example.com`
+    pub fn lookup(&self, aor: &str) -> Vec<Registration> {
+        self.read(|registrations| registrations.get(aor).cloned().unwrap_or_default())
+    }
+
+    /// Returns true if address of record has a registration which hasn't expired
+    pub fn is_registered(&self, aor: &str) -> bool {
+        self.read(|registrations| {
+            registrations
+                .get(aor)
+                .is_some_and(|registrations| registrations.iter().any(|r| !r.is_expired()))
+        })
+    }
+
+    /// Returns registration reached at contact, parameters of contact are ignored
+    pub fn by_contact(&self, contact: &str) -> Option<Registration> {
+        let contact = contact_uri(contact);
+        self.read(|registrations| {
+            registrations
+                .values()
+                .flatten()
+                .find(|registration| registration.contact == contact)
+                .cloned()
+        })
+    }
+
+    /// Returns registrations which expire within given time
+    pub fn expiring_within(&self, within: Duration) -> Vec<Registration> {
+        let deadline = SystemTime::now() + within;
+        self.read(|registrations| {
+            registrations
+                .values()
+                .flatten()
+                .filter(|registration| registration.expires_at <= deadline)
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Returns receiver of changes applied to the registry
+    pub fn changes(&self) -> broadcast::Receiver<RegistrationChange> {
+        self.changes.subscribe()
+    }
+
+    /// Replaces registry with given registrations
+    pub fn seed(&self, seed: Vec<Registration>) {
+        if let Ok(mut registrations) = self.registrations.write() {
+            registrations.clear();
+            for registration in seed {
+                registrations
+                    .entry(registration.aor())
+                    .or_default()
+                    .push(registration);
+            }
+        }
+    }
+
+    /// Updates registry from registration event
+    pub fn apply(&self, event: &RegistrationEvent) {
+        let registration = event.registration().clone();
+        let change = match event.kind() {
+            RegistrationEventKind::Register => {
+                self.insert(registration.clone());
+                Some(RegistrationChange::Registered(registration))
+            }
+            RegistrationEventKind::Unregister => self
+                .remove(&registration)
+                .map(RegistrationChange::Unregistered),
+            RegistrationEventKind::Expire => {
+                self.remove(&registration).map(RegistrationChange::Expired)
+            }
+        };
+        if let Some(change) = change {
+            let _ = self.changes.send(change);
+        }
+    }
+
+    /// Removes expired registrations, reporting them as [`RegistrationChange::Expired`]
+    pub fn remove_expired(&self) -> Vec<Registration> {
+        let Ok(mut registrations) = self.registrations.write() else {
+            return Vec::new();
+        };
+        let mut expired = Vec::new();
+        for aor_registrations in registrations.values_mut() {
+            // expiry is checked once, so every removed registration is reported
+            let (aor_expired, kept): (Vec<_>, Vec<_>) = std::mem::take(aor_registrations)
+                .into_iter()
+                .partition(Registration::is_expired);
+            *aor_registrations = kept;
+            expired.extend(aor_expired);
+        }
+        registrations.retain(|_, aor_registrations| !aor_registrations.is_empty());
+        drop(registrations);
+        for registration in &expired {
+            let _ = self
+                .changes
+                .send(RegistrationChange::Expired(registration.clone()));
+        }
+        expired
+    }
+
+    /// Returns time until the earliest registration expires
+    fn next_expiry(&self) -> Option<Duration> {
+        self.read(|registrations| {
+            registrations
+                .values()
+                .flatten()
+                .map(Registration::expires_in)
+                .min()
+        })
+    }
+
+    fn insert(&self, registration: Registration) {
+        if let Ok(mut registrations) = self.registrations.write() {
+            let aor_registrations = registrations.entry(registration.aor()).or_default();
+            aor_registrations.retain(|existing| !same_binding(existing, &registration));
+            aor_registrations.push(registration);
+        }
+    }
+
+    fn remove(&self, registration: &Registration) -> Option<Registration> {
+        let mut registrations = self.registrations.write().ok()?;
+        let aor = registration.aor();
+        let aor_registrations = registrations.get_mut(&aor)?;
+        let position = aor_registrations
+            .iter()
+            .position(|existing| same_binding(existing, registration))?;
+        let removed = aor_registrations.remove(position);
+        if aor_registrations.is_empty() {
+            registrations.remove(&aor);
+        }
+        Some(removed)
+    }
+
+    fn read<T>(&self, read: impl FnOnce(&HashMap<String, Vec<Registration>>) -> T) -> T
+    where
+        T: Default,
+    {
+        self.registrations
+            .read()
+            .map(|registrations| read(&registrations))
+            .unwrap_or_default()
+    }
+}
+
+/// Registrations are the same binding when they share `Call-ID` or contact
+fn same_binding(existing: &Registration, registration: &Registration) -> bool {
+    match (&existing.call_id, &registration.call_id) {
+        (Some(existing), Some(call_id)) if existing == call_id => true,
+        _ => !registration.contact.is_empty() && existing.contact == registration.contact,
+    }
+}
+
+impl EslConnection {
+    /// returns stream of `sofia::register`, `sofia::unregister` and `sofia::expire` events
+    pub async fn registration_events(&self) -> Result<RegistrationEvents, EslError> {
+        Ok(RegistrationEvents {
+            events: self
+                .subclass_events(&[REGISTER_SUBCLASS, UNREGISTER_SUBCLASS, EXPIRE_SUBCLASS])
+                .await?,
+        })
+    }
+
+    /// returns registry of every registration seeded from `show registrations as json`
+    ///
+    /// Registry is kept current from registration events until connection is closed,
+    /// registrations which aren't refreshed are removed once they expire.
+    pub async fn registration_registry(&self) -> Result<RegistrationRegistry, EslError> {
+        let mut events = self.registration_events().await?;
+        let registry = RegistrationRegistry::new();
+        registry.seed(self.show_registrations().await?);
+        let inner_registry = registry.clone();
+        tokio::spawn(async move {
+            let mut expiry_check = tokio::time::interval(EXPIRY_CHECK_INTERVAL);
+            expiry_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Some(event) => inner_registry.apply(&event),
+                        None => break,
+                    },
+                    _ = expiry_check.tick() => {
+                        if inner_registry.next_expiry() == Some(Duration::ZERO) {
+                            inner_registry.remove_expired();
+                        }
+                    }
+                }
+            }
+        });
+        Ok(registry)
+    }
+
+    /// returns registrations of every profile using `show registrations as json`
+    pub async fn show_registrations(&self) -> Result<Vec<Registration>, EslError> {
+        let registrations = self.api("show registrations as json").await?;
+        let registrations: Value = serde_json::from_str(registrations.trim())?;
+        Ok(registrations
+            .get("rows")
+            .and_then(Value::as_array)
+            .map(|rows| rows.iter().filter_map(Registration::from_row).collect())
+            .unwrap_or_default())
+    }
+
+    /// returns registrations of profile using `sofia status profile <name> reg`
+    pub async fn sofia_registrations(&self, profile: &str) -> Result<Vec<Registration>, EslError> {
+        let status = self
+            .api(&format!("sofia status profile {} reg", profile))
+            .await?;
+        if status.trim().starts_with("Invalid Profile") {
+            return Err(EslError::ApiError(status.trim().to_string()));
+        }
+        Ok(status
+            .split("\n\n")
+            .filter_map(|block| Registration::from_block(profile, block))
+            .collect())
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+use tokio::sync::broadcast;
+
+use crate::conference::{Conference, ConferenceAction, ConferenceEvent, ConferenceMember};
+use crate::{EslConnection, EslError};
+
+const CHANGES_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+/// Members of every conference kept current from `conference::maintenance` events
+///
+/// Created with [`EslConnection::conference_roster`], clones share the same roster.
+pub struct ConferenceRoster {
+    conferences: Arc<RwLock<HashMap<String, Conference>>>,
+    changes: broadcast::Sender<ConferenceEvent>,
+}
+
+impl Default for ConferenceRoster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConferenceRoster {
+    /// Creates empty roster which is updated with [`ConferenceRoster::apply`]
+    pub fn new() -> Self {
+        let (changes, _) = broadcast::channel(CHANGES_CAPACITY);
+        Self {
+            conferences: Arc::new(RwLock::new(HashMap::new())),
+            changes,
+        }
+    }
+
+    /// Returns snapshot of every conference
+    pub fn conferences(&self) -> Vec<Conference> {
+        self.read(|conferences| conferences.values().cloned().collect())
+    }
+
+    /// Returns snapshot of conference with given name
+    pub fn conference(&self, name: &str) -> Option<Conference> {
+        self.read(|conferences| conferences.get(name).cloned())
+    }
+
+    /// Returns members of conference who are currently talking
+    pub fn talking(&self, name: &str) -> Vec<ConferenceMember> {
+        self.read(|conferences| {
+            conferences
+                .get(name)
+                .map(|conference| {
+                    conference
+                        .members
+                        .iter()
+                        .filter(|member| member.flags.talking)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns member of conference who holds the floor
+    pub fn floor(&self, name: &str) -> Option<ConferenceMember> {
+        self.read(|conferences| {
+            conferences
+                .get(name)?
+                .members
+                .iter()
+                .find(|member| member.flags.has_floor)
+                .cloned()
+        })
+    }
+
+    /// Returns receiver of events which were applied to the roster
+    pub fn changes(&self) -> broadcast::Receiver<ConferenceEvent> {
+        self.changes.subscribe()
+    }
+
+    /// Replaces roster with conferences listed by `json_list`
+    pub fn seed(&self, conferences: Vec<Conference>) {
+        if let Ok(mut roster) = self.conferences.write() {
+            *roster = conferences
+                .into_iter()
+                .map(|conference| (conference.name.clone(), conference))
+                .collect();
+        }
+    }
+
+    /// Updates roster from `conference::maintenance` event
+    pub fn apply(&self, event: &ConferenceEvent) {
+        let Ok(mut conferences) = self.conferences.write() else {
+            return;
+        };
+        let name = event.conference_name();
+        match event.action() {
+            ConferenceAction::ConferenceDestroy => {
+                conferences.remove(name);
+            }
+            action => {
+                let conference =
+                    conferences
+                        .entry(name.to_string())
+                        .or_insert_with(|| Conference {
+                            name: name.to_string(),
+                            uuid: None,
+                            member_count: 0,
+                            flags: Vec::new(),
+                            members: Vec::new(),
+                        });
+                if conference.uuid.is_none() {
+                    conference.uuid = event.conference_uuid().map(ToString::to_string);
+                }
+                apply_action(conference, action, event);
+            }
+        }
+        drop(conferences);
+        let _ = self.changes.send(event.clone());
+    }
+
+    fn read<T>(&self, read: impl FnOnce(&HashMap<String, Conference>) -> T) -> T
+    where
+        T: Default,
+    {
+        self.conferences
+            .read()
+            .map(|conferences| read(&conferences))
+            .unwrap_or_default()
+    }
+}
+
+fn apply_action(conference: &mut Conference, action: &ConferenceAction, event: &ConferenceEvent) {
+    match action {
+        ConferenceAction::AddMember => {
+            if let Some(member) = member_from_event(event) {
+                conference
+                    .members
+                    .retain(|existing| existing.id != member.id);
+                conference.members.push(member);
+            }
+        }
+        ConferenceAction::DelMember | ConferenceAction::KickMember => {
+            conference
+                .members
+                .retain(|member| Some(member.id) != event.member_id());
+        }
+        ConferenceAction::FloorChange => {
+            for member in &mut conference.members {
+                member.flags.has_floor = Some(member.id) == event.new_floor();
+            }
+        }
+        ConferenceAction::Lock => {
+            if !conference.is_locked() {
+                conference.flags.push("locked".to_string());
+            }
+        }
+        ConferenceAction::Unlock => conference.flags.retain(|flag| flag != "locked"),
+        action => {
+            let member = conference
+                .members
+                .iter_mut()
+                .find(|member| Some(member.id) == event.member_id());
+            if let (Some(member), Some(flags)) = (member, event.flags()) {
+                let has_floor = member.flags.has_floor;
+                member.flags = flags;
+                // floor is only reported reliably by floor-change
+                member.flags.has_floor = has_floor;
+                match action {
+                    ConferenceAction::StartTalking => member.flags.talking = true,
+                    ConferenceAction::StopTalking => member.flags.talking = false,
+                    ConferenceAction::MuteMember => member.flags.can_speak = false,
+                    ConferenceAction::UnmuteMember => member.flags.can_speak = true,
+                    ConferenceAction::DeafMember => member.flags.can_hear = false,
+                    ConferenceAction::UndeafMember => member.flags.can_hear = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+    conference.member_count = conference.members.len() as u32;
+}
+
+fn member_from_event(event: &ConferenceEvent) -> Option<ConferenceMember> {
+    Some(ConferenceMember {
+        id: event.member_id()?,
+        uuid: event.unique_id().unwrap_or_default().to_string(),
+        channel_name: event.channel_name().map(ToString::to_string),
+        caller_id_name: event.caller_id_name().map(ToString::to_string),
+        caller_id_number: event.caller_id_number().map(ToString::to_string),
+        flags: event.flags().unwrap_or_default(),
+        energy: None,
+        volume_in: None,
+        volume_out: None,
+    })
+}
+
+impl EslConnection {
+    /// returns roster of every conference seeded from `conference json_list`
+    ///
+    /// Roster is kept current from `conference::maintenance` events until connection
+    /// is closed.
+    pub async fn conference_roster(&self) -> Result<ConferenceRoster, EslError> {
+        let mut events = self.conference_events().await?;
+        let roster = ConferenceRoster::new();
+        roster.seed(self.conferences().await?);
+        let inner_roster = roster.clone();
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                inner_roster.apply(&event);
+            }
+        });
+        Ok(roster)
+    }
+
+    /// returns every running conference with its members using `conference json_list`
+    pub async fn conferences(&self) -> Result<Vec<Conference>, EslError> {
+        let list = match self.api("conference json_list").await {
+            Ok(list) => list,
+            Err(EslError::ApiError(error)) if error.contains("No active conferences") => {
+                return Ok(Vec::new())
+            }
+            Err(error) => return Err(error),
+        };
+        let list: Value = serde_json::from_str(list.trim())?;
+        Ok(list
+            .as_array()
+            .map(|conferences| {
+                conferences
+                    .iter()
+                    .filter_map(Conference::from_json)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
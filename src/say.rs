@@ -0,0 +1,304 @@
+use std::fmt::Display;
+
+use crate::{EslConnection, EslError, ExecuteResult};
+
+const SAY_APP: &str = "say";
+const SPEAK_APP: &str = "speak";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Language module used by `say` e.g. `mod_say_en`
+pub enum SayLanguage {
+    /// English
+    En,
+    /// German
+    De,
+    /// Spanish
+    Es,
+    /// French
+    Fr,
+    /// Italian
+    It,
+    /// Dutch
+    Nl,
+    /// Portuguese
+    Pt,
+    /// Russian
+    Ru,
+    /// Swedish
+    Sv,
+    /// Chinese
+    Zh,
+    /// Japanese
+    Ja,
+    /// Hebrew
+    He,
+    /// Any other installed say module
+    Other(String),
+}
+
+impl SayLanguage {
+    /// Returns name of say module
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+            Self::Es => "es",
+            Self::Fr => "fr",
+            Self::It => "it",
+            Self::Nl => "nl",
+            Self::Pt => "pt",
+            Self::Ru => "ru",
+            Self::Sv => "sv",
+            Self::Zh => "zh",
+            Self::Ja => "ja",
+            Self::He => "he",
+            Self::Other(language) => language,
+        }
+    }
+}
+
+impl Display for SayLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+/// What kind of value is spoken by `say`
+pub enum SayType {
+    Number,
+    Items,
+    Persons,
+    Messages,
+    Currency,
+    TimeMeasurement,
+    CurrentDate,
+    CurrentTime,
+    CurrentDateTime,
+    ShortDateTime,
+    TelephoneNumber,
+    TelephoneExtension,
+    Url,
+    IpAddress,
+    EmailAddress,
+    PostalAddress,
+    AccountNumber,
+    NameSpelled,
+    NamePhonetic,
+}
+
+impl SayType {
+    /// Returns say type as expected by `say` application
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Number => "NUMBER",
+            Self::Items => "ITEMS",
+            Self::Persons => "PERSONS",
+            Self::Messages => "MESSAGES",
+            Self::Currency => "CURRENCY",
+            Self::TimeMeasurement => "TIME_MEASUREMENT",
+            Self::CurrentDate => "CURRENT_DATE",
+            Self::CurrentTime => "CURRENT_TIME",
+            Self::CurrentDateTime => "CURRENT_DATE_TIME",
+            Self::ShortDateTime => "SHORT_DATE_TIME",
+            Self::TelephoneNumber => "TELEPHONE_NUMBER",
+            Self::TelephoneExtension => "TELEPHONE_EXTENSION",
+            Self::Url => "URL",
+            Self::IpAddress => "IP_ADDRESS",
+            Self::EmailAddress => "EMAIL_ADDRESS",
+            Self::PostalAddress => "POSTAL_ADDRESS",
+            Self::AccountNumber => "ACCOUNT_NUMBER",
+            Self::NameSpelled => "NAME_SPELLED",
+            Self::NamePhonetic => "NAME_PHONETIC",
+        }
+    }
+}
+
+impl Display for SayType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// How value is spoken by `say`
+pub enum SayMethod {
+    /// Method is not applicable to say type
+    NotApplicable,
+    /// e.g. `one hundred twenty three`
+    Pronounced,
+    /// e.g. `one two three`
+    Iterated,
+    /// e.g. `one hundred twenty third`
+    Counted,
+    /// e.g. `nineteen ninety nine`
+    PronouncedYear,
+}
+
+impl SayMethod {
+    /// Returns say method as expected by `say` application
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::NotApplicable => "N/A",
+            Self::Pronounced => "PRONOUNCED",
+            Self::Iterated => "ITERATED",
+            Self::Counted => "COUNTED",
+            Self::PronouncedYear => "PRONOUNCED_YEAR",
+        }
+    }
+}
+
+impl Display for SayMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+/// Grammatical gender used by languages which inflect numbers
+pub enum SayGender {
+    Feminine,
+    Masculine,
+    Neuter,
+}
+
+impl SayGender {
+    /// Returns gender as expected by `say` application
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Feminine => "FEMININE",
+            Self::Masculine => "MASCULINE",
+            Self::Neuter => "NEUTER",
+        }
+    }
+}
+
+impl Display for SayGender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `say` application
+///
+/// ```rust
+/// use freeswitch_esl::{Say, SayLanguage, SayMethod, SayType};
+///
+/// let options = Say::new(SayType::Currency, "10.50")
+///     .language(SayLanguage::Es)
+///     .method(SayMethod::Pronounced);
+/// ```
+pub struct Say {
+    pub(crate) language: SayLanguage,
+    pub(crate) say_type: SayType,
+    pub(crate) method: SayMethod,
+    pub(crate) gender: Option<SayGender>,
+    pub(crate) text: String,
+}
+
+impl Say {
+    /// Pronounces text as given type in english
+    pub fn new(say_type: SayType, text: &str) -> Self {
+        Self {
+            language: SayLanguage::En,
+            say_type,
+            method: SayMethod::Pronounced,
+            gender: None,
+            text: text.to_string(),
+        }
+    }
+    /// Language module used to speak text
+    pub fn language(mut self, language: SayLanguage) -> Self {
+        self.language = language;
+        self
+    }
+    /// How text is spoken, defaults to [`SayMethod::Pronounced`]
+    pub fn method(mut self, method: SayMethod) -> Self {
+        self.method = method;
+        self
+    }
+    /// Gender used by languages which inflect numbers
+    pub fn gender(mut self, gender: SayGender) -> Self {
+        self.gender = Some(gender);
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        match self.gender {
+            Some(gender) => format!(
+                "{} {} {} {} {}",
+                self.language, self.say_type, self.method, gender, self.text
+            ),
+            None => format!(
+                "{} {} {} {}",
+                self.language, self.say_type, self.method, self.text
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for `speak` application
+///
+/// Without engine and voice text is spoken with `tts_engine` and `tts_voice`
+/// channel variables, see [`EslConnection::set_tts`].
+///
+/// ```rust
+/// use freeswitch_esl::Speak;
+///
+/// let options = Speak::new("Welcome to the conference").engine("flite", "kal");
+/// ```
+pub struct Speak {
+    pub(crate) engine: Option<(String, String)>,
+    pub(crate) text: String,
+}
+
+impl Speak {
+    /// Speaks text using engine and voice set on channel
+    pub fn new(text: &str) -> Self {
+        Self {
+            engine: None,
+            text: text.to_string(),
+        }
+    }
+    /// TTS engine and voice used to speak text e.g. `flite` and `kal`
+    pub fn engine(mut self, engine: &str, voice: &str) -> Self {
+        self.engine = Some((engine.to_string(), voice.to_string()));
+        self
+    }
+
+    pub(crate) fn app_args(&self) -> String {
+        match &self.engine {
+            Some((engine, voice)) => format!("{}|{}|{}", engine, voice, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+impl EslConnection {
+    /// speaks value using `say` application
+    pub async fn say(&self, options: &Say) -> Result<ExecuteResult, EslError> {
+        self.execute(SAY_APP, &options.app_args()).await
+    }
+
+    /// speaks text using `speak` application
+    pub async fn speak(&self, options: &Speak) -> Result<ExecuteResult, EslError> {
+        self.execute(SPEAK_APP, &options.app_args()).await
+    }
+
+    /// sets `tts_engine` and `tts_voice` used by `speak` without engine
+    pub async fn set_tts(&self, engine: &str, voice: &str) -> Result<ExecuteResult, EslError> {
+        self.multiset(&[("tts_engine", engine), ("tts_voice", voice)])
+            .await
+    }
+
+    /// returns `tts_engine` and `tts_voice` set on channel
+    pub async fn tts(&self) -> Result<Option<(String, String)>, EslError> {
+        let engine = self.get_variable("tts_engine").await?;
+        let voice = self.get_variable("tts_voice").await?;
+        Ok(engine.zip(voice))
+    }
+}
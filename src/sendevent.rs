@@ -0,0 +1,80 @@
+use crate::connection::parse_command_reply;
+use crate::{EslConnection, EslError};
+
+const CUSTOM_EVENT: &str = "CUSTOM";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Event fired with [`EslConnection::sendevent`]
+///
+/// ```rust
+/// use freeswitch_esl::EventBuilder;
+///
+/// let event = EventBuilder::custom("myapp::queue_changed")
+///     .header("queue", "support")
+///     .header("waiting", "3")
+///     .body("{\"agents\":2}");
+/// ```
+pub struct EventBuilder {
+    pub(crate) name: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: Option<String>,
+}
+
+impl EventBuilder {
+    /// Builds event with given `Event-Name` e.g. `PRESENCE_IN`
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+    /// Builds `CUSTOM` event with given `Event-Subclass` e.g. `myapp::queue_changed`
+    pub fn custom(subclass: &str) -> Self {
+        Self::new(CUSTOM_EVENT).header("Event-Subclass", subclass)
+    }
+    /// Adds header to event, values must fit on a single line
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+    /// Body of event, sent with `Content-Length`
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    /// Formats `sendevent <name>` followed by headers
+    pub(crate) fn command(&self) -> Result<String, EslError> {
+        let single_line = |value: &str| !value.is_empty() && !value.contains(['\n', '\r']);
+        if !single_line(&self.name) || self.name.contains(' ') {
+            return Err(EslError::InvalidHeader(self.name.clone()));
+        }
+        let mut command = format!("sendevent {}", self.name);
+        for (name, value) in &self.headers {
+            if !single_line(name) || name.contains(':') || value.contains(['\n', '\r']) {
+                return Err(EslError::InvalidHeader(name.clone()));
+            }
+            command.push_str(&format!("\n{}: {}", name, value));
+        }
+        Ok(command)
+    }
+}
+
+impl EslConnection {
+    /// fires event using `sendevent`, returns `Event-UUID` from the reply
+    ///
+    /// Fails with [`EslError::InvalidHeader`] when name or header would break the
+    /// command apart, multi-line content belongs in the body.
+    pub async fn sendevent(&self, event: &EventBuilder) -> Result<String, EslError> {
+        let command = event.command()?;
+        let response = match &event.body {
+            Some(body) => {
+                self.send_recv_with_body(command.as_bytes(), body.as_bytes())
+                    .await?
+            }
+            None => self.send_recv(command.as_bytes()).await?,
+        };
+        parse_command_reply(&response)
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{EslConnection, EslError, Event};
+
+const GATEWAY_STATE_SUBCLASS: &str = "sofia::gateway_state";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Registration state of gateway
+pub enum GatewayState {
+    /// Gateway is not registered
+    Unreged,
+    /// Registration is being sent
+    Trying,
+    /// Gateway is registering
+    Register,
+    /// Gateway is registered
+    Reged,
+    /// Gateway is unregistering
+    Unregister,
+    /// Registration failed
+    Failed,
+    /// Waiting to retry failed registration
+    FailWait,
+    /// Registration expired
+    Expired,
+    /// Gateway is configured not to register
+    Noreg,
+    /// Gateway is down
+    Down,
+    /// Registration timed out
+    Timeout,
+    /// Any other state
+    Other(String),
+}
+
+impl GatewayState {
+    /// Returns state as reported by `sofia status`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Unreged => "UNREGED",
+            Self::Trying => "TRYING",
+            Self::Register => "REGISTER",
+            Self::Reged => "REGED",
+            Self::Unregister => "UNREGISTER",
+            Self::Failed => "FAILED",
+            Self::FailWait => "FAIL_WAIT",
+            Self::Expired => "EXPIRED",
+            Self::Noreg => "NOREG",
+            Self::Down => "DOWN",
+            Self::Timeout => "TIMEOUT",
+            Self::Other(state) => state,
+        }
+    }
+}
+
+impl From<&str> for GatewayState {
+    fn from(state: &str) -> Self {
+        match state {
+            "UNREGED" => Self::Unreged,
+            "TRYING" => Self::Trying,
+            "REGISTER" => Self::Register,
+            "REGED" => Self::Reged,
+            "UNREGISTER" => Self::Unregister,
+            "FAILED" => Self::Failed,
+            "FAIL_WAIT" => Self::FailWait,
+            "EXPIRED" => Self::Expired,
+            "NOREG" => Self::Noreg,
+            "DOWN" => Self::Down,
+            "TIMEOUT" => Self::Timeout,
+            state => Self::Other(state.to_string()),
+        }
+    }
+}
+
+impl Display for GatewayState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Reachability of gateway determined by OPTIONS pings
+pub enum GatewayStatus {
+    /// Gateway answers pings
+    Up,
+    /// Gateway doesn't answer pings
+    Down,
+    /// Any other status
+    Other(String),
+}
+
+impl GatewayStatus {
+    /// Returns status as reported by `sofia status`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Up => "UP",
+            Self::Down => "DOWN",
+            Self::Other(status) => status,
+        }
+    }
+}
+
+impl From<&str> for GatewayStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "UP" => Self::Up,
+            "DOWN" => Self::Down,
+            status => Self::Other(status.to_string()),
+        }
+    }
+}
+
+impl Display for GatewayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Profile listed by `sofia status`
+pub struct SofiaProfile {
+    name: String,
+    url: String,
+    state: String,
+    calls: u32,
+}
+
+impl SofiaProfile {
+    /// Returns name of profile e.g. `internal`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns SIP url profile listens on
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    /// Returns state of profile e.g. `RUNNING`
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+    /// Returns true if profile is running
+    pub fn is_running(&self) -> bool {
+        self.state == "RUNNING"
+    }
+    /// Returns number of calls on profile
+    pub fn calls(&self) -> u32 {
+        self.calls
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Gateway listed by `sofia status`
+pub struct GatewaySummary {
+    profile: String,
+    name: String,
+    url: String,
+    state: GatewayState,
+}
+
+impl GatewaySummary {
+    /// Returns name of profile gateway belongs to
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+    /// Returns name of gateway
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns SIP url of gateway
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    /// Returns registration state of gateway
+    pub fn state(&self) -> &GatewayState {
+        &self.state
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Profiles, gateways and aliases listed by `sofia status`
+pub struct SofiaStatus {
+    profiles: Vec<SofiaProfile>,
+    gateways: Vec<GatewaySummary>,
+    aliases: Vec<(String, String)>,
+}
+
+impl SofiaStatus {
+    /// Returns every profile
+    pub fn profiles(&self) -> &[SofiaProfile] {
+        &self.profiles
+    }
+    /// Returns profile with given name
+    pub fn profile(&self, name: &str) -> Option<&SofiaProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+    /// Returns every gateway of every profile
+    pub fn gateways(&self) -> &[GatewaySummary] {
+        &self.gateways
+    }
+    /// Returns aliases and profiles they point to
+    pub fn aliases(&self) -> &[(String, String)] {
+        &self.aliases
+    }
+
+    /// Parses tab separated `Name Type Data State` table
+    fn from_status(status: &str) -> Self {
+        let mut sofia = Self::default();
+        for line in status.lines() {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [name, kind, data, state] = columns[..] else {
+                continue;
+            };
+            match kind {
+                "profile" => {
+                    let (state, calls) = match state.split_once(" (") {
+                        Some((state, calls)) => {
+                            (state, calls.trim_end_matches(')').parse().unwrap_or(0))
+                        }
+                        None => (state, 0),
+                    };
+                    sofia.profiles.push(SofiaProfile {
+                        name: name.to_string(),
+                        url: data.to_string(),
+                        state: state.to_string(),
+                        calls,
+                    });
+                }
+                "gateway" => {
+                    let (profile, gateway) = name.split_once("::").unwrap_or(("", name));
+                    sofia.gateways.push(GatewaySummary {
+                        profile: profile.to_string(),
+                        name: gateway.to_string(),
+                        url: data.to_string(),
+                        state: GatewayState::from(state.split(' ').next().unwrap_or_default()),
+                    });
+                }
+                "alias" => sofia.aliases.push((name.to_string(), data.to_string())),
+                _ => {}
+            }
+        }
+        sofia
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Gateway details reported by `sofia status gateway <name>`
+pub struct Gateway {
+    name: String,
+    profile: String,
+    realm: Option<String>,
+    username: Option<String>,
+    proxy: Option<String>,
+    state: GatewayState,
+    status: GatewayStatus,
+    ping_frequency: Option<Duration>,
+    ping_time: Option<Duration>,
+    uptime: Option<Duration>,
+    calls_in: u32,
+    calls_out: u32,
+    failed_calls_in: u32,
+    failed_calls_out: u32,
+}
+
+impl Gateway {
+    /// Returns name of gateway
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns name of profile gateway belongs to
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+    /// Returns realm used to register
+    pub fn realm(&self) -> Option<&str> {
+        self.realm.as_deref()
+    }
+    /// Returns username used to register
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+    /// Returns proxy requests are sent to
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+    /// Returns registration state of gateway
+    pub fn state(&self) -> &GatewayState {
+        &self.state
+    }
+    /// Returns whether gateway answers pings
+    pub fn status(&self) -> &GatewayStatus {
+        &self.status
+    }
+    /// Returns how often gateway is pinged, `None` when pings are disabled
+    pub fn ping_frequency(&self) -> Option<Duration> {
+        self.ping_frequency
+    }
+    /// Returns round trip time of last ping
+    pub fn ping_time(&self) -> Option<Duration> {
+        self.ping_time
+    }
+    /// Returns how long gateway has been up
+    pub fn uptime(&self) -> Option<Duration> {
+        self.uptime
+    }
+    /// Returns number of inbound calls through gateway
+    pub fn calls_in(&self) -> u32 {
+        self.calls_in
+    }
+    /// Returns number of outbound calls through gateway
+    pub fn calls_out(&self) -> u32 {
+        self.calls_out
+    }
+    /// Returns number of failed inbound calls through gateway
+    pub fn failed_calls_in(&self) -> u32 {
+        self.failed_calls_in
+    }
+    /// Returns number of failed outbound calls through gateway
+    pub fn failed_calls_out(&self) -> u32 {
+        self.failed_calls_out
+    }
+
+    /// Parses tab separated `Key Value` lines
+    fn from_status(status: &str) -> Option<Self> {
+        let fields: HashMap<&str, &str> = status
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+        let string = |name: &str| {
+            fields
+                .get(name)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let number = |name: &str| {
+            fields
+                .get(name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default()
+        };
+        let seconds = |name: &str| {
+            fields
+                .get(name)
+                .and_then(|value| value.trim_end_matches('s').parse().ok())
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs)
+        };
+        let ping_time = fields
+            .get("PingTime")
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|ms| *ms > 0.0)
+            .map(|ms| Duration::from_secs_f64(ms / 1000.0));
+        Some(Self {
+            name: string("Name")?,
+            profile: string("Profile").unwrap_or_default(),
+            realm: string("Realm"),
+            username: string("Username"),
+            proxy: string("Proxy"),
+            state: GatewayState::from(fields.get("State").copied().unwrap_or_default()),
+            status: GatewayStatus::from(fields.get("Status").copied().unwrap_or_default()),
+            ping_frequency: seconds("PingFreq"),
+            ping_time,
+            uptime: seconds("Uptime"),
+            calls_in: number("CallsIN"),
+            calls_out: number("CallsOUT"),
+            failed_calls_in: number("FailedCallsIN"),
+            failed_calls_out: number("FailedCallsOUT"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Typed `sofia::gateway_state` event
+///
+/// Registration changes carry [`GatewayStateEvent::state`], ping results carry
+/// [`GatewayStateEvent::ping_status`].
+pub struct GatewayStateEvent {
+    gateway: String,
+    state: Option<GatewayState>,
+    ping_status: Option<GatewayStatus>,
+    status_code: Option<u16>,
+    phrase: Option<String>,
+    event: Event,
+}
+
+impl GatewayStateEvent {
+    /// Returns name of gateway
+    pub fn gateway(&self) -> &str {
+        &self.gateway
+    }
+    /// Returns new registration state of gateway
+    pub fn state(&self) -> Option<&GatewayState> {
+        self.state.as_ref()
+    }
+    /// Returns `Ping-Status` of gateway
+    pub fn ping_status(&self) -> Option<&GatewayStatus> {
+        self.ping_status.as_ref()
+    }
+    /// Returns SIP status code of registration response
+    pub fn status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+    /// Returns SIP reason phrase of registration response
+    pub fn phrase(&self) -> Option<&str> {
+        self.phrase.as_deref()
+    }
+    /// Returns raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl TryFrom<Event> for GatewayStateEvent {
+    type Error = EslError;
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let headers = event.json_headers().ok_or_else(|| {
+            EslError::InternalError("body was not found in gateway state event".into())
+        })?;
+        let header = |name: &str| headers.get(name).and_then(Value::as_str);
+        if header("Event-Subclass") != Some(GATEWAY_STATE_SUBCLASS) {
+            return Err(EslError::InternalError(
+                "event is not sofia::gateway_state".into(),
+            ));
+        }
+        Ok(Self {
+            gateway: header("Gateway").unwrap_or_default().to_string(),
+            state: header("State").map(GatewayState::from),
+            ping_status: header("Ping-Status").map(GatewayStatus::from),
+            status_code: header("Status").and_then(|status| status.parse().ok()),
+            phrase: header("Phrase").map(ToString::to_string),
+            event,
+        })
+    }
+}
+
+#[derive(Debug)]
+/// Stream of `sofia::gateway_state` events, created with [`EslConnection::gateway_state_events`]
+pub struct GatewayStateEvents {
+    events: UnboundedReceiver<Event>,
+}
+
+impl GatewayStateEvents {
+    /// Receives next gateway state event, returns `None` once connection is closed
+    pub async fn recv(&mut self) -> Option<GatewayStateEvent> {
+        while let Some(event) = self.events.recv().await {
+            if let Ok(event) = GatewayStateEvent::try_from(event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+impl EslConnection {
+    /// returns stream of `sofia::gateway_state` events of every gateway
+    pub async fn gateway_state_events(&self) -> Result<GatewayStateEvents, EslError> {
+        Ok(GatewayStateEvents {
+            events: self.custom_events(GATEWAY_STATE_SUBCLASS).await?,
+        })
+    }
+
+    /// lists profiles, gateways and aliases using `sofia status`
+    pub async fn sofia_status(&self) -> Result<SofiaStatus, EslError> {
+        let status = self.sofia_api("status").await?;
+        Ok(SofiaStatus::from_status(&status))
+    }
+
+    /// returns details of gateway using `sofia status gateway <name>`
+    pub async fn sofia_gateway(&self, name: &str) -> Result<Gateway, EslError> {
+        let status = self.sofia_api(&format!("status gateway {}", name)).await?;
+        Gateway::from_status(&status)
+            .ok_or_else(|| EslError::ApiError(format!("Invalid Gateway {}", name)))
+    }
+
+    /// restarts profile dropping its calls and reloading its gateways
+    pub async fn sofia_profile_restart(&self, profile: &str) -> Result<(), EslError> {
+        self.sofia_api(&format!("profile {} restart", profile))
+            .await?;
+        Ok(())
+    }
+
+    /// adds gateways added to configuration of profile without restarting it
+    pub async fn sofia_profile_rescan(&self, profile: &str) -> Result<(), EslError> {
+        self.sofia_api(&format!("profile {} rescan", profile))
+            .await?;
+        Ok(())
+    }
+
+    /// unregisters and removes gateway from profile, `all` removes every gateway
+    pub async fn sofia_killgw(&self, profile: &str, gateway: &str) -> Result<(), EslError> {
+        self.sofia_api(&format!("profile {} killgw {}", profile, gateway))
+            .await?;
+        Ok(())
+    }
+
+    /// starts gateway added to configuration of profile
+    pub async fn sofia_startgw(&self, profile: &str, gateway: &str) -> Result<(), EslError> {
+        self.sofia_api(&format!("profile {} startgw {}", profile, gateway))
+            .await?;
+        Ok(())
+    }
+
+    /// sends `sofia <args>` reporting missing profile or gateway as error
+    ///
+    /// mod_sofia doesn't prefix these errors with `-ERR`.
+    async fn sofia_api(&self, args: &str) -> Result<String, EslError> {
+        let response = self.api(&format!("sofia {}", args)).await?;
+        let trimmed = response.trim();
+        if trimmed.starts_with("Invalid Profile") || trimmed.starts_with("Invalid Gateway") {
+            return Err(EslError::ApiError(trimmed.to_string()));
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,446 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::connection::whole_seconds;
+use crate::{EslConnection, EslError, Event, ExecuteResult, HangupCause};
+
+const TRANSFER_APP: &str = "transfer";
+const DEFLECT_APP: &str = "deflect";
+const ATT_XFER_APP: &str = "att_xfer";
+const CHANNEL_BRIDGE: &str = "CHANNEL_BRIDGE";
+const CHANNEL_HANGUP: &str = "CHANNEL_HANGUP";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Leg of bridged call which is transferred
+pub enum TransferLeg {
+    /// Transfers given channel, its bridged leg is hung up
+    #[default]
+    ALeg,
+    /// Transfers channel bridged to given channel with `-bleg`
+    BLeg,
+    /// Transfers both legs with `-both`
+    Both,
+}
+
+impl TransferLeg {
+    /// Returns flag of transfer command, empty for [`TransferLeg::ALeg`]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::ALeg => "",
+            Self::BLeg => "-bleg",
+            Self::Both => "-both",
+        }
+    }
+}
+
+impl Display for TransferLeg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Destination of `uuid_transfer` and `transfer`
+///
+/// ```rust
+/// use freeswitch_esl::{Transfer, TransferLeg};
+///
+/// let transfer = Transfer::new("1000")
+///     .leg(TransferLeg::BLeg)
+///     .dialplan("XML")
+///     .context("default");
+/// ```
+pub struct Transfer {
+    pub(crate) destination: String,
+    pub(crate) leg: TransferLeg,
+    pub(crate) dialplan: Option<String>,
+    pub(crate) context: Option<String>,
+}
+
+impl Transfer {
+    /// Transfers call to extension using dialplan and context of the channel
+    pub fn new(destination: &str) -> Self {
+        Self {
+            destination: destination.to_string(),
+            leg: TransferLeg::ALeg,
+            dialplan: None,
+            context: None,
+        }
+    }
+    /// Leg which is transferred
+    pub fn leg(mut self, leg: TransferLeg) -> Self {
+        self.leg = leg;
+        self
+    }
+    /// Dialplan used to route extension e.g. `XML`
+    pub fn dialplan(mut self, dialplan: &str) -> Self {
+        self.dialplan = Some(dialplan.to_string());
+        self
+    }
+    /// Context used to route extension, defaults dialplan to `XML`
+    pub fn context(mut self, context: &str) -> Self {
+        self.context = Some(context.to_string());
+        self
+    }
+
+    /// Formats `[-bleg|-both] <destination> [<dialplan>] [<context>]`
+    pub(crate) fn args(&self) -> String {
+        let mut args = Vec::with_capacity(4);
+        if self.leg != TransferLeg::ALeg {
+            args.push(self.leg.as_str());
+        }
+        args.push(&self.destination);
+        match (&self.dialplan, &self.context) {
+            (Some(dialplan), Some(context)) => args.extend([dialplan.as_str(), context.as_str()]),
+            (Some(dialplan), None) => args.push(dialplan),
+            (None, Some(context)) => args.extend(["XML", context.as_str()]),
+            (None, None) => {}
+        }
+        args.join(" ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How transfer ended
+pub enum TransferOutcome {
+    /// Caller was handed over to transfer target
+    Completed,
+    /// Transfer target hung up or transfer was cancelled, caller stays with transferor
+    Cancelled,
+    /// Transfer target could not be reached
+    Failed(HangupCause),
+    /// Call hung up before transfer could complete
+    Abandoned(HangupCause),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Outcome of `att_xfer` application
+pub struct AttendedTransferResult {
+    outcome: TransferOutcome,
+    target_uuid: Option<String>,
+    result: Option<ExecuteResult>,
+}
+
+impl AttendedTransferResult {
+    /// Returns whether transfer completed, was cancelled or failed
+    pub fn outcome(&self) -> &TransferOutcome {
+        &self.outcome
+    }
+    /// Returns `Unique-ID` of consulted channel from `last_bridge_to`
+    pub fn target_uuid(&self) -> Option<&str> {
+        self.target_uuid.as_deref()
+    }
+    /// Returns result of the application, missing when transferor hung up to complete transfer
+    pub fn execute_result(&self) -> Option<&ExecuteResult> {
+        self.result.as_ref()
+    }
+
+    pub(crate) fn new(result: ExecuteResult) -> Self {
+        let non_empty = |name: &str| {
+            result
+                .variable(name)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let disposition = non_empty("originate_disposition");
+        let outcome = match disposition.as_deref() {
+            Some("SUCCESS") | None if result.hungup() => TransferOutcome::Completed,
+            Some("SUCCESS") | None => TransferOutcome::Cancelled,
+            Some(cause) => TransferOutcome::Failed(HangupCause::from(cause)),
+        };
+        Self {
+            outcome,
+            target_uuid: non_empty("last_bridge_to"),
+            result: Some(result),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Consultation of attended transfer controlled with uuid commands
+///
+/// Agent is bridged to transfer target while customer waits parked, see
+/// [`EslConnection::consult`].
+///
+/// ```rust
+/// use std::time::Duration;
+/// use freeswitch_esl::ConsultTransfer;
+///
+/// let consult = ConsultTransfer::new("customer-uuid", "agent-uuid", "user/1001")
+///     .hold_music("local_stream://moh")
+///     .timeout(Duration::from_secs(20));
+/// ```
+pub struct ConsultTransfer {
+    pub(crate) customer_uuid: String,
+    pub(crate) agent_uuid: String,
+    pub(crate) dial_string: String,
+    pub(crate) target_uuid: Option<String>,
+    pub(crate) hold_music: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl ConsultTransfer {
+    /// Consults target reached with dial-string while agent is talking to customer
+    pub fn new(customer_uuid: &str, agent_uuid: &str, dial_string: &str) -> Self {
+        Self {
+            customer_uuid: customer_uuid.to_string(),
+            agent_uuid: agent_uuid.to_string(),
+            dial_string: dial_string.to_string(),
+            target_uuid: None,
+            hold_music: None,
+            timeout: None,
+        }
+    }
+    /// `Unique-ID` of transfer target, generated when not given
+    pub fn target_uuid(mut self, target_uuid: &str) -> Self {
+        self.target_uuid = Some(target_uuid.to_string());
+        self
+    }
+    /// File or stream played to customer during consultation
+    pub fn hold_music(mut self, hold_music: &str) -> Self {
+        self.hold_music = Some(hold_music.to_string());
+        self
+    }
+    /// Time to wait for target to answer, set as `originate_timeout` rounded up to whole seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Ongoing consultation started with [`EslConnection::consult`]
+pub struct Consultation {
+    customer_uuid: String,
+    agent_uuid: String,
+    target_uuid: String,
+    hold_music: bool,
+}
+
+impl Consultation {
+    /// Returns `Unique-ID` of waiting customer
+    pub fn customer_uuid(&self) -> &str {
+        &self.customer_uuid
+    }
+    /// Returns `Unique-ID` of transferring agent
+    pub fn agent_uuid(&self) -> &str {
+        &self.agent_uuid
+    }
+    /// Returns `Unique-ID` of transfer target
+    pub fn target_uuid(&self) -> &str {
+        &self.target_uuid
+    }
+}
+
+#[derive(Debug)]
+/// Stream of consultation outcome, created with [`EslConnection::transfer_events`]
+pub struct TransferEvents {
+    events: UnboundedReceiver<Event>,
+    consultation: Consultation,
+    finished: bool,
+}
+
+impl TransferEvents {
+    /// Waits until customer is bridged to target, back to agent, or hangs up
+    ///
+    /// Returns `None` once the outcome was received or connection is closed.
+    pub async fn recv(&mut self) -> Option<TransferOutcome> {
+        if self.finished {
+            return None;
+        }
+        while let Some(event) = self.events.recv().await {
+            if let Some(outcome) = self.outcome(&event) {
+                self.finished = true;
+                return Some(outcome);
+            }
+        }
+        None
+    }
+
+    fn outcome(&self, event: &Event) -> Option<TransferOutcome> {
+        let headers = event.json_headers()?;
+        let header = |name: &str| headers.get(name).and_then(Value::as_str);
+        let consultation = &self.consultation;
+        let unique_id = header("Unique-ID")?;
+        match header("Event-Name")? {
+            CHANNEL_BRIDGE => {
+                let legs = (unique_id, header("Other-Leg-Unique-ID")?);
+                let bridged =
+                    |first: &str, second: &str| legs == (first, second) || legs == (second, first);
+                if bridged(&consultation.customer_uuid, &consultation.target_uuid) {
+                    Some(TransferOutcome::Completed)
+                } else if bridged(&consultation.agent_uuid, &consultation.customer_uuid) {
+                    Some(TransferOutcome::Cancelled)
+                } else {
+                    None
+                }
+            }
+            CHANNEL_HANGUP if unique_id == consultation.target_uuid => {
+                Some(TransferOutcome::Cancelled)
+            }
+            CHANNEL_HANGUP if unique_id == consultation.customer_uuid => {
+                let cause = header("Hangup-Cause")
+                    .map(HangupCause::from)
+                    .unwrap_or(HangupCause::None);
+                Some(TransferOutcome::Abandoned(cause))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl EslConnection {
+    /// blind transfers call to another extension using `transfer` application
+    pub async fn transfer(&self, transfer: &Transfer) -> Result<ExecuteResult, EslError> {
+        self.execute(TRANSFER_APP, &transfer.args()).await
+    }
+
+    /// redirects SIP call to given uri using REFER, or 302 when call isn't answered
+    ///
+    /// Call is hung up with [`HangupCause::BlindTransfer`] once the endpoint accepts
+    /// REFER, which can be awaited with [`EslConnection::on_hangup`] in outbound mode.
+    pub async fn deflect(&self, uri: &str) -> Result<ExecuteResult, EslError> {
+        self.execute(DEFLECT_APP, uri).await
+    }
+
+    /// attended transfer of call bridged to this channel using `att_xfer` application
+    ///
+    /// Target is consulted first, transfer completes when this channel hangs up once
+    /// bridged to target and is cancelled when target hangs up. Hanging up before
+    /// target answered is reported as [`TransferOutcome::Abandoned`].
+    pub async fn att_xfer(&self, dial_string: &str) -> Result<AttendedTransferResult, EslError> {
+        let mut events = self.call_events().await?;
+        match self.execute(ATT_XFER_APP, dial_string).await {
+            Ok(result) => Ok(AttendedTransferResult::new(result)),
+            Err(EslError::ChannelHungUp(hangup_cause)) => {
+                let mut target_uuid = None;
+                while let Ok(event) = events.try_recv() {
+                    let Some(headers) = event.json_headers() else {
+                        continue;
+                    };
+                    let header = |name: &str| headers.get(name).and_then(Value::as_str);
+                    if header("Event-Name") == Some(CHANNEL_BRIDGE) {
+                        target_uuid = header("Other-Leg-Unique-ID").map(ToString::to_string);
+                    }
+                }
+                let outcome = match target_uuid {
+                    Some(_) => TransferOutcome::Completed,
+                    None => TransferOutcome::Abandoned(hangup_cause),
+                };
+                Ok(AttendedTransferResult {
+                    outcome,
+                    target_uuid,
+                    result: None,
+                })
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// starts consultation by parking customer and bridging agent to transfer target
+    ///
+    /// Fails with [`EslError::OriginateFailed`] when target can't be reached, customer
+    /// and agent stay bridged in that case.
+    pub async fn consult(&self, options: &ConsultTransfer) -> Result<Consultation, EslError> {
+        let target_uuid = options
+            .target_uuid
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let mut variables = format!("origination_uuid={}", target_uuid);
+        if let Some(timeout) = options.timeout {
+            variables.push_str(&format!(",originate_timeout={}", whole_seconds(timeout)));
+        }
+        let originate = format!("originate {{{}}}{} &park()", variables, options.dial_string);
+        if let Err(error) = self.bgapi(&originate).await {
+            return Err(match error {
+                EslError::ApiError(cause) => {
+                    EslError::OriginateFailed(HangupCause::from(cause.trim()))
+                }
+                error => error,
+            });
+        }
+        for uuid in [&options.customer_uuid, &options.agent_uuid] {
+            self.uuid_setvar(uuid, "park_after_bridge", "true").await?;
+        }
+        self.uuid_bridge(&options.agent_uuid, &target_uuid).await?;
+        if let Some(hold_music) = &options.hold_music {
+            self.uuid_broadcast(&options.customer_uuid, hold_music)
+                .await?;
+        }
+        Ok(Consultation {
+            customer_uuid: options.customer_uuid.clone(),
+            agent_uuid: options.agent_uuid.clone(),
+            target_uuid,
+            hold_music: options.hold_music.is_some(),
+        })
+    }
+
+    /// returns stream reporting how consultation ends, also when customer or target hang up
+    ///
+    /// Subscribes `CHANNEL_BRIDGE` and `CHANNEL_HANGUP` events, so it should be created
+    /// right after [`EslConnection::consult`].
+    pub async fn transfer_events(
+        &self,
+        consultation: &Consultation,
+    ) -> Result<TransferEvents, EslError> {
+        Ok(TransferEvents {
+            events: self.named_events(&[CHANNEL_BRIDGE, CHANNEL_HANGUP]).await?,
+            consultation: consultation.clone(),
+            finished: false,
+        })
+    }
+
+    /// completes consultation by bridging customer to transfer target
+    ///
+    /// Agent is left parked and can be hung up with [`EslConnection::uuid_kill`]. When
+    /// transfer target already hung up agent is bridged back to customer and
+    /// [`TransferOutcome::Failed`] is returned.
+    pub async fn complete_transfer(
+        &self,
+        consultation: &Consultation,
+    ) -> Result<TransferOutcome, EslError> {
+        if consultation.hold_music {
+            self.uuid_break_all(&consultation.customer_uuid).await?;
+        }
+        match self
+            .uuid_bridge(&consultation.customer_uuid, &consultation.target_uuid)
+            .await
+        {
+            Ok(()) => Ok(TransferOutcome::Completed),
+            // error doesn't tell which leg is gone, customer is only reconnected to agent
+            // when transfer target hung up
+            Err(EslError::NoSuchChannel(_)) => {
+                if self.uuid_exists(&consultation.target_uuid).await? {
+                    return Err(EslError::NoSuchChannel(consultation.customer_uuid.clone()));
+                }
+                self.uuid_bridge(&consultation.agent_uuid, &consultation.customer_uuid)
+                    .await?;
+                Ok(TransferOutcome::Failed(HangupCause::NormalClearing))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// cancels consultation hanging up transfer target and bridging agent back to customer
+    pub async fn cancel_transfer(
+        &self,
+        consultation: &Consultation,
+    ) -> Result<TransferOutcome, EslError> {
+        if consultation.hold_music {
+            self.uuid_break_all(&consultation.customer_uuid).await?;
+        }
+        match self
+            .uuid_kill(&consultation.target_uuid, &HangupCause::NormalClearing)
+            .await
+        {
+            Ok(()) | Err(EslError::NoSuchChannel(_)) => {}
+            Err(error) => return Err(error),
+        }
+        self.uuid_bridge(&consultation.agent_uuid, &consultation.customer_uuid)
+            .await?;
+        Ok(TransferOutcome::Cancelled)
+    }
+}
@@ -1,4 +1,5 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use ntest::timeout;
 use regex::Regex;
@@ -9,14 +10,25 @@ use tokio::{
 };
 
 use anyhow::Result;
-use freeswitch_esl::{Esl, EslError};
+use freeswitch_esl::{
+    AgentState, AgentStatus, AgentType, AnswerState, CallcenterAction, ConferenceAction, Esl,
+    EslError, EventBuilder, ExecuteOptions, GatewayState, GatewayStatus, HangupCause, HoldAction,
+    MemberSelector, Presence, PresenceEventKind, PresenceProbe, Registration, RegistrationChange,
+    RegistrationEventKind, TierState, Transfer, TransferLeg,
+};
 
 async fn mock_test_server() -> Result<(JoinHandle<()>, SocketAddr)> {
     let listener = TcpListener::bind("localhost:0").await?;
     let local_address = listener.local_addr()?;
+    let bgapi_re = Regex::new(r"(?P<bgapi>.+)\nJob-UUID: (?P<uuid>[0-9a-fA-F-]+)")?;
+    let sendmsg_re = Regex::new(
+        r"(?s)sendmsg (?P<call>\S+)\n.*execute-app-name: (?P<app>[^\n]*)\n.*execute-app-arg: (?P<arg>[^\n]*)\n.*Event-UUID: (?P<uuid>[0-9a-fA-F-]+)",
+    )?;
     let server = tokio::spawn(async move {
         loop {
             let (mut socket, _) = listener.accept().await.unwrap();
+            let bgapi_re = bgapi_re.clone();
+            let sendmsg_re = sendmsg_re.clone();
             tokio::spawn(async move {
                 let _ = socket.write_all(b"Content-Type: auth/request\n\n").await;
 
@@ -41,15 +53,27 @@ async fn mock_test_server() -> Result<(JoinHandle<()>, SocketAddr)> {
                         // Convert the data to a string for comparison
                         let mut data_string =
                             String::from_utf8_lossy(data_before_newlines).to_string();
+                        // Body of sendevent follows the headers without blank line
+                        let body_length = data_string
+                            .lines()
+                            .find_map(|line| line.strip_prefix("Content-Length: "))
+                            .and_then(|length| length.parse::<usize>().ok())
+                            .unwrap_or(0);
+                        let message_end = index + 2 + body_length;
+                        if received_data.len() < message_end {
+                            break;
+                        }
+                        if body_length > 0 {
+                            let body =
+                                String::from_utf8_lossy(&received_data[index + 2..message_end]);
+                            data_string = format!("{}\n\n{}", data_string, body);
+                        }
 
                         // HACK
                         let response_text: Vec<String> = if data_string.starts_with("bgapi")
                             && data_string.contains("Job-UUID")
                         {
-                            let re =
-                                Regex::new(r"(?P<bgapi>.+)\nJob-UUID: (?P<uuid>[0-9a-fA-F-]+)")
-                                    .unwrap();
-                            let captures = re.captures(&data_string).unwrap();
+                            let captures = bgapi_re.captures(&data_string).unwrap();
                             // Extract components
                             let _ = &captures["bgapi"];
                             let uuid_old = &captures["uuid"];
@@ -75,6 +99,202 @@ async fn mock_test_server() -> Result<(JoinHandle<()>, SocketAddr)> {
                             } else {
                                 panic!("Unhandled application")
                             }
+                        } else if let Some(captures) = sendmsg_re.captures(&data_string) {
+                            let call_uuid = &captures["call"];
+                            if call_uuid == "karan" {
+                                vec![
+                                    "Content-Type: command/reply\nReply-Text: +OK\n\n".to_string(),
+                                    execute_complete(
+                                        call_uuid,
+                                        &captures["uuid"],
+                                        &captures["app"],
+                                        &captures["arg"],
+                                    ),
+                                ]
+                            } else {
+                                vec!["Content-Type: command/reply\nReply-Text: -ERR invalid session id [unknown]\n\n".to_string()]
+                            }
+                        } else if data_string == "event json CUSTOM callcenter::info" {
+                            let events = [
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "callcenter::info",
+                                    "CC-Queue": "support@default",
+                                    "CC-Action": "member-queue-start",
+                                    "CC-Member-UUID": "member-1",
+                                    "CC-Member-Session-UUID": "karan",
+                                    "CC-Member-CID-Name": "Karan",
+                                    "CC-Member-CID-Number": "1000",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "callcenter::info",
+                                    "CC-Queue": "support@default",
+                                    "CC-Action": "members-count",
+                                    "CC-Count": "1",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "callcenter::info",
+                                    "CC-Queue": "support@default",
+                                    "CC-Action": "agent-offering",
+                                    "CC-Agent": "1000@default",
+                                    "CC-Member-UUID": "member-1",
+                                    "CC-Member-Session-UUID": "karan",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "callcenter::info",
+                                    "CC-Queue": "support@default",
+                                    "CC-Action": "bridge-agent-start",
+                                    "CC-Agent": "1000@default",
+                                    "CC-Agent-UUID": "agent-leg",
+                                    "CC-Member-Session-UUID": "karan",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "callcenter::info",
+                                    "CC-Queue": "support@default",
+                                    "CC-Action": "bridge-agent-end",
+                                    "CC-Agent": "1000@default",
+                                    "CC-Agent-UUID": "agent-leg",
+                                    "CC-Hangup-Cause": "NORMAL_CLEARING",
+                                }),
+                            ];
+                            event_frames("+OK event listener enabled json", &events)
+                        } else if data_string == "event json CUSTOM sofia::gateway_state" {
+                            let events = [
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "sofia::gateway_state",
+                                    "Gateway": "provider",
+                                    "State": "FAILED",
+                                    "Status": "403",
+                                    "Phrase": "Forbidden",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "sofia::gateway_state",
+                                    "Gateway": "provider",
+                                    "Ping-Status": "DOWN",
+                                }),
+                            ];
+                            event_frames("+OK event listener enabled json", &events)
+                        } else if data_string
+                            == "event json CUSTOM sofia::register sofia::unregister sofia::expire"
+                        {
+                            let events = [
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "sofia::register",
+                                    "profile-name": "internal",
+                                    "from-host": "example.com",
+                                    "from-user": "1002",
+                                    "contact": "\"1002\" <sip:1002@10.0.0.9:5062;ob>",
+                                    "call-id": "c1002",
+                                    "status": "Registered(UDP)",
+                                    "expires": "3600",
+                                    "network-ip": "10.0.0.9",
+                                    "network-port": "5062",
+                                    "user-agent": "Zoiper",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "sofia::unregister",
+                                    "profile-name": "internal",
+                                    "from-host": "example.com",
+                                    "from-user": "1001",
+                                    "contact": "<sip:1001@10.0.0.8:5060>",
+                                    "call-id": "c1001",
+                                    "expires": "0",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "sofia::expire",
+                                    "profile-name": "internal",
+                                    "user": "1003",
+                                    "username": "1003",
+                                    "host": "example.com",
+                                    "contact": "<sip:1003@10.0.0.10:5060>",
+                                    "call-id": "c1003",
+                                    "expires": "0",
+                                }),
+                            ];
+                            event_frames("+OK event listener enabled json", &events)
+                        } else if data_string
+                            == "event json PRESENCE_IN PRESENCE_OUT PRESENCE_PROBE"
+                        {
+                            event_frames(
+                                "+OK event listener enabled json",
+                                &[serde_json::json!({
+                                    "Event-Name": "PRESENCE_IN",
+                                    "proto": "sip",
+                                    "from": "1000@example.com",
+                                    "login": "sofia/internal/1000@example.com",
+                                    "status": "Ringing",
+                                    "rpid": "unknown",
+                                    "event_type": "presence",
+                                    "alt_event_type": "dialog",
+                                    "answer-state": "early",
+                                    "Unique-ID": "karan",
+                                    "presence-call-direction": "inbound",
+                                })],
+                            )
+                        } else if data_string == "event json CUSTOM conference::maintenance" {
+                            let events = [
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "conference::maintenance",
+                                    "Action": "add-member",
+                                    "Conference-Name": "3000",
+                                    "Conference-Unique-ID": "c0nf-3000",
+                                    "Member-ID": "7",
+                                    "Member-Type": "moderator",
+                                    "Unique-ID": "karan",
+                                    "Caller-Caller-ID-Name": "Karan",
+                                    "Caller-Caller-ID-Number": "1000",
+                                    "Hear": "true",
+                                    "Speak": "true",
+                                    "Talking": "false",
+                                    "Floor": "false",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "conference::maintenance",
+                                    "Action": "floor-change",
+                                    "Conference-Name": "3000",
+                                    "Old-ID": "none",
+                                    "New-ID": "7",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "conference::maintenance",
+                                    "Action": "start-talking",
+                                    "Conference-Name": "3000",
+                                    "Member-ID": "7",
+                                    "Hear": "true",
+                                    "Speak": "true",
+                                    "Talking": "true",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "conference::maintenance",
+                                    "Action": "mute-member",
+                                    "Conference-Name": "3000",
+                                    "Member-ID": "7",
+                                    "Hear": "true",
+                                    "Speak": "false",
+                                    "Talking": "true",
+                                }),
+                                serde_json::json!({
+                                    "Event-Name": "CUSTOM",
+                                    "Event-Subclass": "conference::maintenance",
+                                    "Action": "del-member",
+                                    "Conference-Name": "3000",
+                                    "Member-ID": "8",
+                                }),
+                            ];
+                            event_frames("+OK event listener enabled json", &events)
                         } else {
                             // data_string.contains("Job-UUID")
 
@@ -97,6 +317,136 @@ async fn mock_test_server() -> Result<(JoinHandle<()>, SocketAddr)> {
                             "api uuid_kill karan" => {
                                 "Content-Type: api/response\nContent-Length: 4\n\n+OK\n"
                             }
+                            "api uuid_setvar_multi karan greeting=hello\\; world;lang=en" => {
+                                "Content-Type: api/response\nContent-Length: 4\n\n+OK\n"
+                            }
+                            "api uuid_getvar karan lang" => {
+                                "Content-Type: api/response\nContent-Length: 2\n\nen"
+                            }
+                            "api uuid_getvar karan missing" => {
+                                "Content-Type: api/response\nContent-Length: 7\n\n_undef_"
+                            }
+                            "api uuid_dump karan json" => {
+                                "Content-Type: api/response\nContent-Length: 71\n\n{\"Unique-ID\":\"karan\",\"Channel-State\":\"CS_EXECUTE\",\"variable_lang\":\"en\"}"
+                            }
+                            "api uuid_transfer karan -bleg 1000 XML default"
+                            | "api uuid_bridge karan other"
+                            | "api uuid_break karan all"
+                            | "api uuid_hold toggle karan"
+                            | "api uuid_hold off karan"
+                            | "api uuid_park karan"
+                            | "api uuid_kill karan USER_BUSY"
+                            | "api uuid_answer karan"
+                            | "api uuid_pre_answer karan" => {
+                                "Content-Type: api/response\nContent-Length: 4\n\n+OK\n"
+                            }
+                            "api uuid_transfer missing 1000" => {
+                                "Content-Type: api/response\nContent-Length: 22\n\n-ERR No such channel!\n"
+                            }
+                            "api uuid_exists karan" => {
+                                "Content-Type: api/response\nContent-Length: 4\n\ntrue"
+                            }
+                            "api uuid_exists missing" => {
+                                "Content-Type: api/response\nContent-Length: 5\n\nfalse"
+                            }
+                            "api conference list" => {
+                                "Content-Type: api/response\nContent-Length: 275\n\n+OK Conference 3000 (2 members rate: 8000 flags: running|answered|enforce_min|dynamic|exit_sound|enter_sound|locked)\n7;sofia/internal/1000@example.com;karan;Karan;1000;hear|speak|talking|floor|moderator;0;0;300\n8;sofia/internal/1001@example.com;other;Other;1001;hear;0;0;300\n"
+                            }
+                            "api conference json_list" => {
+                                "Content-Type: api/response\nContent-Length: 301\n\n[{\"conference_name\":\"3000\",\"member_count\":2,\"conference_uuid\":\"c0nf-3000\",\"running\":true,\"members\":[{\"type\":\"caller\",\"id\":7,\"uuid\":\"karan\",\"flags\":{\"can_hear\":true,\"can_speak\":false}},{\"type\":\"caller\",\"id\":8,\"uuid\":\"other\",\"flags\":{\"can_hear\":true,\"can_speak\":true,\"talking\":true,\"has_floor\":true}}]}]"
+                            }
+                            "api conference 3000 json_list" => {
+                                "Content-Type: api/response\nContent-Length: 348\n\n[{\"conference_name\":\"3000\",\"member_count\":1,\"conference_uuid\":\"c0nf-3000\",\"running\":true,\"locked\":false,\"members\":[{\"type\":\"caller\",\"id\":7,\"uuid\":\"karan\",\"caller_id_name\":\"Karan\",\"caller_id_number\":\"1000\",\"energy\":300,\"volume_in\":0,\"volume_out\":0,\"flags\":{\"can_hear\":true,\"can_speak\":false,\"talking\":false,\"has_floor\":true,\"is_moderator\":false}}]}]"
+                            }
+                            "api conference 3000 mute 7"
+                            | "api conference 3000 kick all"
+                            | "api conference 3000 play ivr/ivr-welcome.wav 7"
+                            | "api conference 3000 record /tmp/3000.wav"
+                            | "api conference 3000 lock"
+                            | "api conference 3000 floor 7" => {
+                                "Content-Type: api/response\nContent-Length: 3\n\nOK\n"
+                            }
+                            "api conference 3000 mute 9" => {
+                                "Content-Type: api/response\nContent-Length: 17\n\nNon-Existant ID 9\n"
+                            }
+                            "api conference 4000 json_list" => {
+                                "Content-Type: api/response\nContent-Length: 26\n\nConference 4000 not found\n"
+                            }
+                            "api uuid_record karan start /tmp/karan.wav 60"
+                            | "api uuid_record karan start /tmp/karan.wav 1"
+                            | "api uuid_record karan mask /tmp/karan.wav"
+                            | "api uuid_record karan unmask /tmp/karan.wav"
+                            | "api uuid_record karan stop /tmp/karan.wav" => {
+                                "Content-Type: api/response\nContent-Length: 12\n\n+OK Success\n"
+                            }
+                            "api uuid_record karan stop /tmp/missing.wav" => {
+                                "Content-Type: api/response\nContent-Length: 29\n\n-ERR Cannot locate recording\n"
+                            }
+                            "api callcenter_config agent add 1000@default callback"
+                            | "api callcenter_config agent set status 1000@default 'Available (On Demand)'"
+                            | "api callcenter_config agent set contact 1000@default user/1000"
+                            | "api callcenter_config tier add support@default 1000@default 1 1"
+                            | "api callcenter_config tier set state support@default 1000@default 'Ready'"
+                            | "api callcenter_config queue reload support@default" => {
+                                "Content-Type: api/response\nContent-Length: 4\n\n+OK\n"
+                            }
+                            "api callcenter_config agent del 2000@default" => {
+                                "Content-Type: api/response\nContent-Length: 20\n\n-ERR Invalid Agent!\n"
+                            }
+                            "api callcenter_config agent list" => {
+                                "Content-Type: api/response\nContent-Length: 418\n\nname|instance_id|uuid|type|contact|status|state|max_no_answer|wrap_up_time|reject_delay_time|busy_delay_time|no_answer_delay_time|last_bridge_start|last_bridge_end|last_offered_call|last_status_change|no_answer_count|calls_answered|talk_time|ready_time|external_calls_count\n1000@default|single_box||callback|[leg_timeout=10]user/1000@default|Available (On Demand)|Waiting|3|10|10|60|0|0|0|0|1694493097|0|4|320|0|0\n+OK\n"
+                            }
+                            "api callcenter_config tier list" => {
+                                "Content-Type: api/response\nContent-Length: 119\n\nqueue|agent|state|level|position\nsupport@default|1000@default|Ready|1|1\nsupport@default|1001@default|No Answer|2|1\n+OK\n"
+                            }
+                            "api callcenter_config queue list" => {
+                                "Content-Type: api/response\nContent-Length: 450\n\nname|strategy|moh_sound|time_base_score|tier_rules_apply|tier_rule_wait_second|tier_rule_wait_multiply_level|tier_rule_no_agent_no_wait|discard_abandoned_after|abandoned_resume_allowed|max_wait_time|max_wait_time_with_no_agent|max_wait_time_with_no_agent_time_reached|record_template|calls_answered|calls_abandoned|ring_progressively_delay\nsupport@default|longest-idle-agent|local_stream://moh|system|false|300|true|false|60|false|0|0|5||12|3|10\n+OK\n"
+                            }
+                            "api callcenter_config queue count members support@default" => {
+                                "Content-Type: api/response\nContent-Length: 2\n\n2\n"
+                            }
+                            "api sofia status" => {
+                                "Content-Type: api/response\nContent-Length: 634\n\n                     Name\t   Type\t                                      Data\tState\n=================================================================================================\n                 external\tprofile\t           sip:mod_sofia@10.0.0.5:5080\tRUNNING (0)\n       external::provider\tgateway\t                 sip:trunk@sip.provider.com\tREGED\n              10.0.0.5\t  alias\t                                  internal\tALIASED\n                 internal\tprofile\t           sip:mod_sofia@10.0.0.5:5060\tRUNNING (2)\n=================================================================================================\n2 profiles 1 alias\n"
+                            }
+                            "api sofia status gateway provider" => {
+                                "Content-Type: api/response\nContent-Length: 536\n\n=================================================================================================\nName    \tprovider\nProfile \texternal\nScheme  \tDigest\nRealm   \tsip.provider.com\nUsername\ttrunk\nPassword\tyes\nProxy   \tsip:sip.provider.com\nContext \tpublic\nExpires \t3600\nFreq    \t3600\nPing    \t1694493157\nPingFreq\t30\nPingTime\t24.50\nPingState\t0/0/0\nState   \tREGED\nStatus  \tUP\nUptime  \t3600s\nCallsIN \t5\nCallsOUT\t7\nFailedCallsIN\t1\nFailedCallsOUT\t2\n=================================================================================================\n"
+                            }
+                            "api sofia status gateway missing" => {
+                                "Content-Type: api/response\nContent-Length: 17\n\nInvalid Gateway!\n"
+                            }
+                            "api sofia profile external rescan" => {
+                                "Content-Type: api/response\nContent-Length: 18\n\n+OK scan complete\n"
+                            }
+                            "api sofia profile external killgw provider" => {
+                                "Content-Type: api/response\nContent-Length: 33\n\n+OK gateway marked for deletion.\n"
+                            }
+                            "api sofia profile external startgw provider" => {
+                                "Content-Type: api/response\nContent-Length: 4\n\n+OK\n"
+                            }
+                            "api sofia profile missing rescan" => {
+                                "Content-Type: api/response\nContent-Length: 26\n\nInvalid Profile [missing]\n"
+                            }
+                            "api show registrations as json" => {
+                                "Content-Type: api/response\nContent-Length: 488\n\n{\"row_count\":2,\"rows\":[{\"reg_user\":\"1000\",\"realm\":\"example.com\",\"token\":\"c1000\",\"url\":\"sofia/internal/sip:1000@10.0.0.7:5060;fs_nat=yes\",\"expires\":\"4102444800\",\"network_ip\":\"10.0.0.7\",\"network_port\":\"5060\",\"network_proto\":\"udp\",\"hostname\":\"fs1\",\"metadata\":\"\"},{\"reg_user\":\"1001\",\"realm\":\"example.com\",\"token\":\"c1001\",\"url\":\"sofia/internal/sip:1001@10.0.0.8:5060\",\"expires\":\"4102444800\",\"network_ip\":\"10.0.0.8\",\"network_port\":\"5060\",\"network_proto\":\"udp\",\"hostname\":\"fs1\",\"metadata\":\"\"}]}\n"
+                            }
+                            "api sofia status profile internal reg" => {
+                                "Content-Type: api/response\nContent-Length: 599\n\nRegistrations:\n=================================================================================================\nCall-ID:    \tc1000\nUser:       \t1000@example.com\nContact:    \t\"1000\" <sip:1000@10.0.0.7:5060;ob>\nAgent:      \tZoiper\nStatus:     \tRegistered(UDP)(unknown) EXP(2023-09-12 05:31:37) EXPSECS(3590)\nPing-Status:\tReachable\nPing-Time:\t0.00\nHost:       \tfs1\nIP:         \t10.0.0.7\nPort:       \t5060\nAuth-User:  \t1000\nAuth-Realm: \texample.com\nMWI-Account:\t1000@example.com\n\nTotal items returned: 1\n=================================================================================================\n"
+                            }
+                            "sendevent PRESENCE_IN\nproto: sip\nfrom: 1000@example.com\nlogin: 1000@example.com\nstatus: On the phone\nrpid: unknown\nevent_type: presence\nalt_event_type: dialog\nevent_count: 1\nunique-id: karan\nchannel-state: CS_ROUTING\nanswer-state: confirmed\npresence-call-direction: outbound"
+                            | "sendevent PRESENCE_PROBE\nproto: sip\nlogin: 1001@example.com\nfrom: 1001@example.com\nto: 1000@example.com\nevent_type: presence" => {
+                                "Content-Type: command/reply\nReply-Text: +OK 7f4de4bc-17d7-11dd-b7a0-db4edd065621\n\n"
+                            }
+                            "sendevent CUSTOM\nEvent-Subclass: myapp::queue_changed\nqueue: support\nContent-Length: 12\n\n{\"agents\":2}"
+                            | "sendevent CUSTOM\nEvent-Subclass: myapp::queue_changed\nqueue: sales" => {
+                                "Content-Type: command/reply\nReply-Text: +OK 0d7c3b6e-4f4e-4d6b-9d2b-7a3c6e1f2a90\n\n"
+                            }
+                            "filter Unique-ID karan" => {
+                                // channel headers are url encoded and may contain ':'
+                                "Content-Type: command/reply\nReply-Text: +OK Job-UUID: 14f61274-6487-4b79-b97b-ee0feca07e86\nChannel-Unique-ID: karan\nCaller-Caller-ID-Name: Karan%20Gauswami\nCaller-Destination-Number: sip%3A1000%40example.com\nVariable-Accent: Ren%C3%A9\nVariable-Discount: 100%25\nVariable-Malformed: 50%zz%2\nVariable-Trailing: 100%\nVariable-Empty:\n\n"
+                            }
+                            "filter Unique-ID missing" => {
+                                "Content-Type: command/reply\nReply-Text: -ERR 100%25 of +1 failed\n\n"
+                            }
                             "event json BACKGROUND_JOB CHANNEL_EXECUTE_COMPLETE"=>{
                                 "Content-Type: command/reply\nReply-Text: +OK event listener enabled json\n\n"
                             }
@@ -121,7 +471,7 @@ async fn mock_test_server() -> Result<(JoinHandle<()>, SocketAddr)> {
                         }
 
                         // Remove the processed data from the received_data buffer
-                        received_data.drain(0..=index + 1);
+                        received_data.drain(0..message_end);
                     }
                 }
             });
@@ -129,6 +479,40 @@ async fn mock_test_server() -> Result<(JoinHandle<()>, SocketAddr)> {
     });
     Ok((server, local_address))
 }
+fn event_frames(reply: &str, events: &[serde_json::Value]) -> Vec<String> {
+    let mut frames = vec![format!(
+        "Content-Type: command/reply\nReply-Text: {}\n\n",
+        reply
+    )];
+    for event in events {
+        let body = event.to_string();
+        frames.push(format!(
+            "Content-Length: {}\nContent-Type: text/event-json\n\n{}",
+            body.len(),
+            body
+        ));
+    }
+    frames
+}
+fn execute_complete(call_uuid: &str, event_uuid: &str, app: &str, app_data: &str) -> String {
+    let body = serde_json::json!({
+        "Event-Name": "CHANNEL_EXECUTE_COMPLETE",
+        "Unique-ID": call_uuid,
+        "Channel-State": "CS_EXECUTE",
+        "Answer-State": "answered",
+        "Application": app,
+        "Application-Data": app_data,
+        "Application-Response": "_none_",
+        "Application-UUID": event_uuid,
+    })
+    .to_string();
+    format!(
+        "Content-Length: {}\nContent-Type: text/event-json\n\n{}",
+        body.len(),
+        body
+    )
+}
+
 #[tokio::test]
 #[timeout(1000)]
 async fn reloadxml() -> Result<()> {
@@ -176,6 +560,46 @@ async fn send_recv_test() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[timeout(10000)]
+async fn header_decoding() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let response = inbound.send_recv(b"filter Unique-ID karan").await?;
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.as_str())
+    };
+    assert_eq!(
+        Some("+OK Job-UUID: 14f61274-6487-4b79-b97b-ee0feca07e86"),
+        header("Reply-Text")
+    );
+    assert_eq!(Some("Karan Gauswami"), header("Caller-Caller-ID-Name"));
+    assert_eq!(
+        Some("sip:1000@example.com"),
+        header("Caller-Destination-Number")
+    );
+    assert_eq!(Some("René"), header("Variable-Accent"));
+    assert_eq!(Some("100%"), header("Variable-Discount"));
+    // malformed escapes are kept as they are
+    assert_eq!(Some("50%zz%2"), header("Variable-Malformed"));
+    assert_eq!(Some("100%"), header("Variable-Trailing"));
+    assert_eq!(Some(""), header("Variable-Empty"));
+    // other replies aren't channel headers, their values are kept as sent
+    let response = inbound.send_recv(b"filter Unique-ID missing").await?;
+    assert_eq!(
+        Some("-ERR 100%25 of +1 failed"),
+        response
+            .headers()
+            .get("Reply-Text")
+            .and_then(|value| value.as_str())
+    );
+    Ok(())
+}
+
 #[tokio::test]
 #[timeout(10000)]
 async fn wrong_password() -> Result<()> {
@@ -223,6 +647,36 @@ async fn concurrent_api() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[timeout(10000)]
+async fn concurrent_call_handles() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let mut tasks = Vec::new();
+    for _ in 0..50 {
+        let call = inbound.call("karan");
+        tasks.push(tokio::spawn(async move {
+            assert_eq!(
+                Some("en".to_string()),
+                call.get_variable("lang").await.unwrap()
+            );
+        }));
+        let call = inbound.call("missing");
+        tasks.push(tokio::spawn(async move {
+            assert!(!call.uuid_exists("missing").await.unwrap());
+        }));
+        let call = inbound.call("karan");
+        tasks.push(tokio::spawn(async move {
+            assert!(call.uuid_exists("karan").await.unwrap());
+        }));
+    }
+    for task in tasks {
+        task.await?;
+    }
+    Ok(())
+}
+
 #[tokio::test]
 #[timeout(10000)]
 async fn concurrent_bgapi() -> Result<()> {
@@ -282,3 +736,528 @@ async fn uuid_kill() -> Result<()> {
     assert_eq!("", uuid_kill_response);
     Ok(())
 }
+
+#[tokio::test]
+#[timeout(10000)]
+async fn execute_on_call_handle() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let call = inbound.call("karan");
+    let mut events = call.call_events().await?;
+    let mut other_events = inbound.call("other").call_events().await?;
+    let result = call.answer().await?;
+    assert_eq!("answer", result.application());
+    assert_eq!(None, result.response());
+    assert!(!result.hungup());
+    let event = events.recv().await.unwrap();
+    assert!(event
+        .body()
+        .as_ref()
+        .unwrap()
+        .contains("\"Unique-ID\":\"karan\""));
+    assert!(other_events.try_recv().is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn execute_without_call_uuid() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    assert_eq!(Err(EslError::NoCallUuid), inbound.answer().await);
+    let response = inbound.call("other").playback("ivr/ivr-welcome.wav").await;
+    assert_eq!(
+        Err(EslError::ApiError("invalid session id [unknown]".into())),
+        response
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn execute_async_on_call_handle() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let call = inbound.call("karan");
+    let mut events = call.call_events().await?;
+    let options = ExecuteOptions::new().loops(3).event_lock(true);
+    let application_uuid = call
+        .execute_async_with("playback", "local_stream://moh", &options)
+        .await?;
+    let event = events.recv().await.unwrap();
+    assert!(event
+        .body()
+        .as_ref()
+        .unwrap()
+        .contains(&format!("\"Application-UUID\":\"{}\"", application_uuid)));
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn uuid_variables() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    inbound
+        .uuid_setvar_multi("karan", &[("greeting", "hello; world"), ("lang", "en")])
+        .await?;
+    assert_eq!(
+        Some("en".to_string()),
+        inbound.uuid_getvar("karan", "lang").await?
+    );
+    assert_eq!(None, inbound.uuid_getvar("karan", "missing").await?);
+    let dump = inbound.uuid_dump("karan").await?;
+    assert_eq!(
+        Some("CS_EXECUTE"),
+        dump.get("Channel-State").map(String::as_str)
+    );
+    assert_eq!(Some("en"), dump.get("variable_lang").map(String::as_str));
+    assert_eq!(
+        Err(EslError::InvalidVariable("bad name".into())),
+        inbound.uuid_setvar("karan", "bad name", "value").await
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn uuid_record() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    inbound
+        .uuid_record_start("karan", "/tmp/karan.wav", Some(Duration::from_secs(60)))
+        .await?;
+    // a limit under a second isn't sent as 0, which would be unlimited
+    inbound
+        .uuid_record_start("karan", "/tmp/karan.wav", Some(Duration::from_millis(200)))
+        .await?;
+    inbound.uuid_record_mask("karan", "/tmp/karan.wav").await?;
+    inbound
+        .uuid_record_unmask("karan", "/tmp/karan.wav")
+        .await?;
+    inbound.uuid_record_stop("karan", "/tmp/karan.wav").await?;
+    assert_eq!(
+        Err(EslError::ApiError("Cannot locate recording".into())),
+        inbound.uuid_record_stop("karan", "/tmp/missing.wav").await
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn uuid_call_control() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let transfer = Transfer::new("1000")
+        .leg(TransferLeg::BLeg)
+        .context("default");
+    inbound.uuid_transfer("karan", &transfer).await?;
+    assert_eq!(
+        Err(EslError::NoSuchChannel("missing".into())),
+        inbound
+            .uuid_transfer("missing", &Transfer::new("1000"))
+            .await
+    );
+    inbound.uuid_bridge("karan", "other").await?;
+    inbound.uuid_break_all("karan").await?;
+    inbound.uuid_hold("karan", HoldAction::Toggle).await?;
+    inbound.uuid_hold("karan", HoldAction::Off).await?;
+    inbound.uuid_park("karan").await?;
+    inbound.uuid_answer("karan").await?;
+    inbound.uuid_pre_answer("karan").await?;
+    inbound.uuid_kill("karan", &HangupCause::UserBusy).await?;
+    assert!(inbound.uuid_exists("karan").await?);
+    assert!(!inbound.uuid_exists("missing").await?);
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn conference_control() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let conferences = inbound.conference_list().await?;
+    assert_eq!(1, conferences.len());
+    let conference = &conferences[0];
+    assert_eq!("3000", conference.name());
+    assert_eq!(2, conference.member_count());
+    assert!(conference.is_locked());
+    let member = conference.member(7).expect("member 7 is listed");
+    assert_eq!("karan", member.uuid());
+    assert_eq!(
+        Some("sofia/internal/1000@example.com"),
+        member.channel_name()
+    );
+    assert!(member.flags().talking());
+    assert!(member.flags().is_moderator());
+    assert!(!conference.members()[1].flags().can_speak());
+
+    let conference = inbound.conference("3000").await?;
+    assert_eq!(Some("c0nf-3000"), conference.uuid());
+    assert!(!conference.is_locked());
+    let member = conference.member(7).expect("member 7 is listed");
+    assert!(member.flags().has_floor());
+    assert!(!member.flags().can_speak());
+    assert_eq!(Some(300), member.energy());
+    assert_eq!(
+        Err(EslError::ApiError("Conference 4000 not found".into())),
+        inbound.conference("4000").await
+    );
+
+    inbound
+        .conference_mute("3000", MemberSelector::Id(7))
+        .await?;
+    assert_eq!(
+        Err(EslError::ApiError("Non-Existant ID 9".into())),
+        inbound.conference_mute("3000", 9.into()).await
+    );
+    inbound.conference_kick("3000", MemberSelector::All).await?;
+    inbound
+        .conference_play("3000", "ivr/ivr-welcome.wav", Some(7))
+        .await?;
+    inbound.conference_record("3000", "/tmp/3000.wav").await?;
+    inbound.conference_lock("3000").await?;
+    inbound.conference_floor("3000", 7).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn conference_events() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let mut events = inbound.conference_events().await?;
+    let event = events.recv().await.expect("add-member event");
+    assert_eq!(&ConferenceAction::AddMember, event.action());
+    assert_eq!("3000", event.conference_name());
+    assert_eq!(Some(7), event.member_id());
+    assert_eq!(Some("karan"), event.unique_id());
+    let flags = event.flags().expect("member flags");
+    assert!(flags.can_speak());
+    assert!(flags.is_moderator());
+    let event = events.recv().await.expect("floor-change event");
+    assert_eq!(&ConferenceAction::FloorChange, event.action());
+    assert_eq!(None, event.old_floor());
+    assert_eq!(Some(7), event.new_floor());
+    assert_eq!(None, event.flags());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn conference_roster() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let roster = inbound.conference_roster().await?;
+    // maintenance events are sent right after subscribing, del-member is the last one
+    while roster
+        .conference("3000")
+        .is_some_and(|conference| conference.member(8).is_some())
+    {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let conference = roster.conference("3000").expect("conference is seeded");
+    assert_eq!(1, conference.member_count());
+    let member = conference.member(7).expect("member 7 was added");
+    assert_eq!(Some("Karan"), member.caller_id_name());
+    assert!(member.flags().talking());
+    assert!(!member.flags().can_speak());
+    assert_eq!(Some(7), roster.floor("3000").map(|member| member.id()));
+    let talking: Vec<u32> = roster
+        .talking("3000")
+        .iter()
+        .map(|member| member.id())
+        .collect();
+    assert_eq!(vec![7], talking);
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn callcenter_config() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    inbound
+        .callcenter_agent_add("1000@default", AgentType::Callback)
+        .await?;
+    inbound
+        .callcenter_agent_status("1000@default", &AgentStatus::AvailableOnDemand)
+        .await?;
+    inbound
+        .callcenter_agent_contact("1000@default", "user/1000")
+        .await?;
+    inbound
+        .callcenter_tier_add("support@default", "1000@default", 1, 1)
+        .await?;
+    inbound
+        .callcenter_tier_state("support@default", "1000@default", &TierState::Ready)
+        .await?;
+    inbound.callcenter_queue_reload("support@default").await?;
+    assert_eq!(
+        Err(EslError::ApiError("Invalid Agent!".into())),
+        inbound.callcenter_agent_del("2000@default").await
+    );
+
+    let agents = inbound.callcenter_agents().await?;
+    assert_eq!(1, agents.len());
+    assert_eq!("1000@default", agents[0].name());
+    assert_eq!("[leg_timeout=10]user/1000@default", agents[0].contact());
+    assert_eq!(&AgentStatus::AvailableOnDemand, agents[0].status());
+    assert_eq!(&AgentState::Waiting, agents[0].state());
+    assert_eq!(4, agents[0].calls_answered());
+    assert_eq!(Duration::from_secs(320), agents[0].talk_time());
+
+    let tiers = inbound.callcenter_tiers().await?;
+    assert_eq!(2, tiers.len());
+    assert_eq!(&TierState::NoAnswer, tiers[1].state());
+    assert_eq!(2, tiers[1].level());
+
+    let queues = inbound.callcenter_queues().await?;
+    assert_eq!(1, queues.len());
+    assert_eq!("longest-idle-agent", queues[0].strategy());
+    assert_eq!(3, queues[0].calls_abandoned());
+    assert_eq!(2, inbound.callcenter_queue_count("support@default").await?);
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn callcenter_events() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let mut events = inbound.callcenter_events().await?;
+    let event = events.recv().await.expect("member-queue-start event");
+    assert_eq!(&CallcenterAction::MemberQueueStart, event.action());
+    assert_eq!(Some("support@default"), event.queue());
+    assert_eq!(Some("karan"), event.member_session_uuid());
+    assert_eq!(Some("Karan"), event.member_caller_id_name());
+    let event = events.recv().await.expect("members-count event");
+    assert_eq!(&CallcenterAction::MembersCount, event.action());
+    assert_eq!(Some(1), event.count());
+    let event = events.recv().await.expect("agent-offering event");
+    assert_eq!(&CallcenterAction::AgentOffering, event.action());
+    assert_eq!(Some("1000@default"), event.agent());
+    let event = events.recv().await.expect("bridge-agent-start event");
+    assert_eq!(&CallcenterAction::BridgeAgentStart, event.action());
+    assert_eq!(Some("agent-leg"), event.agent_uuid());
+    let event = events.recv().await.expect("bridge-agent-end event");
+    assert_eq!(&CallcenterAction::BridgeAgentEnd, event.action());
+    assert_eq!(Some(&HangupCause::NormalClearing), event.hangup_cause());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn sofia_status() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let status = inbound.sofia_status().await?;
+    assert_eq!(2, status.profiles().len());
+    let internal = status.profile("internal").expect("internal profile");
+    assert!(internal.is_running());
+    assert_eq!(2, internal.calls());
+    assert_eq!("sip:mod_sofia@10.0.0.5:5060", internal.url());
+    assert_eq!(1, status.gateways().len());
+    assert_eq!("external", status.gateways()[0].profile());
+    assert_eq!("provider", status.gateways()[0].name());
+    assert_eq!(&GatewayState::Reged, status.gateways()[0].state());
+    assert_eq!(
+        &[("10.0.0.5".to_string(), "internal".to_string())],
+        status.aliases()
+    );
+
+    let gateway = inbound.sofia_gateway("provider").await?;
+    assert_eq!("external", gateway.profile());
+    assert_eq!(Some("trunk"), gateway.username());
+    assert_eq!(&GatewayState::Reged, gateway.state());
+    assert_eq!(&GatewayStatus::Up, gateway.status());
+    assert_eq!(Some(Duration::from_secs(30)), gateway.ping_frequency());
+    assert_eq!(Some(Duration::from_micros(24500)), gateway.ping_time());
+    assert_eq!(Some(Duration::from_secs(3600)), gateway.uptime());
+    assert_eq!(1, gateway.failed_calls_in());
+    assert_eq!(2, gateway.failed_calls_out());
+    assert_eq!(
+        Err(EslError::ApiError("Invalid Gateway!".into())),
+        inbound.sofia_gateway("missing").await
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn sofia_profile_control() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    inbound.sofia_profile_restart("external").await?;
+    inbound.sofia_profile_rescan("external").await?;
+    inbound.sofia_killgw("external", "provider").await?;
+    inbound.sofia_startgw("external", "provider").await?;
+    assert_eq!(
+        Err(EslError::ApiError("Invalid Profile [missing]".into())),
+        inbound.sofia_profile_rescan("missing").await
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn gateway_state_events() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let mut events = inbound.gateway_state_events().await?;
+    let event = events.recv().await.expect("registration event");
+    assert_eq!("provider", event.gateway());
+    assert_eq!(Some(&GatewayState::Failed), event.state());
+    assert_eq!(Some(403), event.status_code());
+    assert_eq!(Some("Forbidden"), event.phrase());
+    assert_eq!(None, event.ping_status());
+    let event = events.recv().await.expect("ping event");
+    assert_eq!(None, event.state());
+    assert_eq!(Some(&GatewayStatus::Down), event.ping_status());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn registration_events() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let mut events = inbound.registration_events().await?;
+    let event = events.recv().await.expect("register event");
+    assert_eq!(RegistrationEventKind::Register, event.kind());
+    assert_eq!(Some("Registered(UDP)"), event.status());
+    let registration = event.registration();
+    assert_eq!("1002@example.com", registration.aor());
+    assert_eq!("sip:1002@10.0.0.9:5062", registration.contact());
+    assert_eq!(Some("internal"), registration.profile());
+    assert_eq!(Some(5062), registration.network_port());
+    assert!(registration.expires_in() > Duration::from_secs(3500));
+    let event = events.recv().await.expect("unregister event");
+    assert_eq!(RegistrationEventKind::Unregister, event.kind());
+    assert_eq!("1001", event.registration().user());
+    let event = events.recv().await.expect("expire event");
+    assert_eq!(RegistrationEventKind::Expire, event.kind());
+    assert_eq!("1003@example.com", event.registration().aor());
+    assert_eq!("sip:1003@10.0.0.10:5060", event.registration().contact());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn registration_registry() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let registry = inbound.registration_registry().await?;
+    let mut changes = registry.changes();
+    let change = changes.recv().await?;
+    assert!(matches!(change, RegistrationChange::Registered(_)));
+    assert_eq!("sip:1002@10.0.0.9:5062", change.registration().contact());
+    let change = changes.recv().await?;
+    assert!(matches!(change, RegistrationChange::Unregistered(_)));
+    assert_eq!("1001@example.com", change.registration().aor());
+
+    assert!(registry.is_registered("1000@example.com"));
+    assert!(!registry.is_registered("1001@example.com"));
+    assert!(registry.is_registered("1002@example.com"));
+    assert_eq!(2, registry.registrations().len());
+    let registration = registry
+        .by_contact("<sip:1000@10.0.0.7:5060;transport=udp>")
+        .expect("seeded registration");
+    assert_eq!("1000@example.com", registration.aor());
+    assert_eq!(Some("c1000"), registration.call_id());
+    let expiring: Vec<String> = registry
+        .expiring_within(Duration::from_secs(7200))
+        .iter()
+        .map(Registration::aor)
+        .collect();
+    assert_eq!(vec!["1002@example.com".to_string()], expiring);
+
+    let registrations = inbound.sofia_registrations("internal").await?;
+    assert_eq!(1, registrations.len());
+    assert_eq!("sip:1000@10.0.0.7:5060", registrations[0].contact());
+    assert_eq!(Some("Zoiper"), registrations[0].user_agent());
+    assert!(registrations[0].expires_in() > Duration::from_secs(3500));
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn send_presence() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let presence = Presence::new("1000@example.com")
+        .status("On the phone")
+        .answer_state(AnswerState::Confirmed)
+        .unique_id("karan");
+    assert_eq!(
+        "7f4de4bc-17d7-11dd-b7a0-db4edd065621",
+        inbound.send_presence(&presence).await?
+    );
+    let probe = PresenceProbe::new("1001@example.com", "1000@example.com");
+    inbound.send_presence_probe(&probe).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn presence_events() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let mut events = inbound.presence_events().await?;
+    let event = events.recv().await.expect("presence event");
+    assert_eq!(PresenceEventKind::In, event.kind());
+    assert_eq!(Some("1000@example.com"), event.from());
+    assert_eq!(Some("Ringing"), event.status());
+    assert_eq!(Some(&AnswerState::Early), event.answer_state());
+    assert_eq!(Some("karan"), event.unique_id());
+    assert_eq!(Some("inbound"), event.direction());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn sendevent() -> Result<()> {
+    let (_, addr) = mock_test_server().await?;
+    let stream = TcpStream::connect(addr).await?;
+    let inbound = Esl::inbound(stream, "ClueCon").await?;
+    let event = EventBuilder::custom("myapp::queue_changed")
+        .header("queue", "support")
+        .body("{\"agents\":2}");
+    assert_eq!(
+        "0d7c3b6e-4f4e-4d6b-9d2b-7a3c6e1f2a90",
+        inbound.sendevent(&event).await?
+    );
+    // commands after an event with body are framed correctly
+    let event = EventBuilder::custom("myapp::queue_changed").header("queue", "sales");
+    inbound.sendevent(&event).await?;
+    let event =
+        EventBuilder::custom("myapp::queue_changed").header("queue", "support\nfake: header");
+    assert_eq!(
+        Err(EslError::InvalidHeader("queue".into())),
+        inbound.sendevent(&event).await
+    );
+    let event = EventBuilder::new("PRESENCE_IN").header("bad:name", "value");
+    assert_eq!(
+        Err(EslError::InvalidHeader("bad:name".into())),
+        inbound.sendevent(&event).await
+    );
+    Ok(())
+}
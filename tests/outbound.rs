@@ -0,0 +1,1206 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ntest::timeout;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use anyhow::Result;
+use freeswitch_esl::{
+    Bridge, CallDirection, CollectDigits, CollectStatus, ConsultTransfer, DigitsStatus, Esl,
+    EslConnection, EslError, ExecuteOptions, HangupCause, IvrAction, IvrMenu, IvrOutcome,
+    OutboundOptions, PlayAndGetDigits, Playback, PlaybackStatus, ReadDigits, Record,
+    RecordEventKind, RecordSession, Say, SayGender, SayLanguage, SayMethod, SayType, Speak,
+    Transfer, TransferOutcome,
+};
+
+const CALL_UUID: &str = "7f4de4bc-17d7-11dd-b7a0-db4edd065621";
+
+const CONNECT_REPLY: &str = "Content-Type: command/reply
+Reply-Text: +OK
+Channel-Unique-ID: 7f4de4bc-17d7-11dd-b7a0-db4edd065621
+Channel-Name: sofia/internal/1000%40192.168.1.100
+Channel-State: CS_EXECUTE
+Call-Direction: inbound
+Caller-Caller-ID-Name: Karan%20Gauswami
+Caller-Caller-ID-Number: 1000
+Caller-Network-Addr: 192.168.1.100
+Caller-Destination-Number: 5000
+Caller-Context: default
+Channel-Read-Codec-Name: PCMU
+Channel-Write-Codec-Name: PCMA
+variable_sip_h_X-Account-ID: 42
+variable_sip_contact_uri: sip:1000@192.168.1.100:5060
+variable_language: en
+
+";
+
+/// Parses `sendmsg` command into headers
+fn parse_command(command: &str) -> HashMap<String, String> {
+    command
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn event_json(body: serde_json::Value) -> String {
+    let body = body.to_string();
+    format!(
+        "Content-Length: {}\nContent-Type: text/event-json\n\n{}",
+        body.len(),
+        body
+    )
+}
+
+fn execute_complete(command: &HashMap<String, String>, variables: serde_json::Value) -> String {
+    let mut body = serde_json::json!({
+        "Event-Name": "CHANNEL_EXECUTE_COMPLETE",
+        "Unique-ID": CALL_UUID,
+        "Channel-State": "CS_EXECUTE",
+        "Answer-State": "answered",
+        "Application": command["execute-app-name"],
+        "Application-Data": command["execute-app-arg"],
+        "Application-Response": "_none_",
+        "Application-UUID": command["Event-UUID"],
+    });
+    if let (Some(body), Some(variables)) = (body.as_object_mut(), variables.as_object()) {
+        body.extend(variables.clone());
+    }
+    event_json(body)
+}
+
+fn channel_hangup_complete(hangup_cause: &str) -> String {
+    event_json(serde_json::json!({
+        "Event-Name": "CHANNEL_HANGUP_COMPLETE",
+        "Unique-ID": CALL_UUID,
+        "Channel-State": "CS_REPORTING",
+        "Answer-State": "hangup",
+        "Hangup-Cause": hangup_cause,
+        "variable_billsec": "42",
+    }))
+}
+
+fn channel_hangup(hangup_cause: &str) -> String {
+    event_json(serde_json::json!({
+        "Event-Name": "CHANNEL_HANGUP",
+        "Unique-ID": CALL_UUID,
+        "Channel-State": "CS_HANGUP",
+        "Answer-State": "hangup",
+        "Hangup-Cause": hangup_cause,
+    }))
+}
+
+/// Simulates freeswitch connecting to outbound socket, replying to `sendmsg`
+/// commands using given handler
+async fn mock_outbound_call<F>(handler: F) -> Result<EslConnection>
+where
+    F: FnMut(&str, &HashMap<String, String>) -> Vec<String> + Send + 'static,
+{
+    mock_outbound_call_with(&OutboundOptions::new(), handler).await
+}
+
+/// Same as [`mock_outbound_call`] but accepts connection with given options
+async fn mock_outbound_call_with<F>(
+    options: &OutboundOptions,
+    mut handler: F,
+) -> Result<EslConnection>
+where
+    F: FnMut(&str, &HashMap<String, String>) -> Vec<String> + Send + 'static,
+{
+    let listener = TcpListener::bind("localhost:0").await?;
+    let local_address = listener.local_addr()?;
+    tokio::spawn(async move {
+        let mut socket = TcpStream::connect(local_address).await.unwrap();
+        let mut buffer = [0; 1024];
+        let mut received_data = Vec::new();
+        loop {
+            let n = match socket.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(_) => break,
+            };
+            received_data.extend_from_slice(&buffer[0..n]);
+            while let Some(index) = received_data
+                .windows(2)
+                .position(|window| window == b"\n\n")
+            {
+                let data_string = String::from_utf8_lossy(&received_data[0..index]).to_string();
+                let responses = match data_string.as_ref() {
+                    "connect" => vec![CONNECT_REPLY.to_string()],
+                    "exit" => vec![
+                        "Content-Type: command/reply\nReply-Text: +OK bye\n\n".to_string(),
+                        "Content-Type: text/disconnect-notice\nContent-Disposition: disconnect\n\n"
+                            .to_string(),
+                    ],
+                    command
+                        if command.starts_with("event json") || command.starts_with("myevents") =>
+                    {
+                        vec!["Content-Type: command/reply\nReply-Text: +OK\n\n".to_string()]
+                    }
+                    command => {
+                        let mut responses = Vec::new();
+                        if command.starts_with("sendmsg")
+                            || command.starts_with("linger")
+                            || command.starts_with("nolinger")
+                        {
+                            responses.push(
+                                "Content-Type: command/reply\nReply-Text: +OK\n\n".to_string(),
+                            );
+                        }
+                        responses.extend(handler(command, &parse_command(command)));
+                        responses
+                    }
+                };
+                for response in responses {
+                    if socket.write_all(response.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+                received_data.drain(0..=index + 1);
+            }
+        }
+    });
+    let (socket, _) = listener.accept().await?;
+    Ok(Esl::outbound_with(socket, options).await?)
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_execute() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        vec![execute_complete(
+            command,
+            serde_json::json!({"Application-Response": "FILE PLAYED"}),
+        )]
+    })
+    .await?;
+    assert_eq!(Some(CALL_UUID.to_string()), conn.call_uuid().await);
+    assert!(conn.connected());
+    let result = conn.playback("ivr/ivr-welcome.wav").await?;
+    assert_eq!("playback", result.application());
+    assert_eq!("ivr/ivr-welcome.wav", result.application_data());
+    assert_eq!(Some("FILE PLAYED"), result.response());
+    assert_eq!(None, conn.hangup_cause());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn execute_result_response_fallback() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| match command["execute-app-name"].as_ref() {
+        "set" => vec![execute_complete(command, serde_json::json!({}))],
+        "bridge" => vec![execute_complete(
+            command,
+            serde_json::json!({"variable_current_application_response": "USER_BUSY"}),
+        )],
+        _ => vec![execute_complete(
+            command,
+            serde_json::json!({
+                "Application-Response": "FILE PLAYED",
+                "variable_current_application_response": "stale",
+            }),
+        )],
+    })
+    .await?;
+    let result = conn.execute("set", "lang=en").await?;
+    assert_eq!(None, result.response());
+    let result = conn.execute("bridge", "user/1001").await?;
+    assert_eq!(Some("USER_BUSY"), result.response());
+    let result = conn.execute("playback", "ivr/ivr-welcome.wav").await?;
+    assert_eq!(Some("FILE PLAYED"), result.response());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn execute_result_hungup() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        let states = match command["execute-app-arg"].as_ref() {
+            "answer-state" => serde_json::json!({"Answer-State": "hangup"}),
+            "channel-state" => serde_json::json!({"Channel-State": "CS_HANGUP"}),
+            "reporting" => serde_json::json!({"Channel-State": "CS_REPORTING"}),
+            _ => serde_json::json!({}),
+        };
+        vec![execute_complete(command, states)]
+    })
+    .await?;
+    assert!(!conn.execute("log", "answered").await?.hungup());
+    assert!(conn.execute("log", "answer-state").await?.hungup());
+    assert!(conn.execute("log", "channel-state").await?.hungup());
+    assert!(conn.execute("log", "reporting").await?.hungup());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_execute_loops() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        let loops: u32 = command["loops"].parse().unwrap();
+        (1..=loops)
+            .map(|iteration| {
+                execute_complete(
+                    command,
+                    serde_json::json!({"Application-Response": format!("iteration {}", iteration)}),
+                )
+            })
+            .collect()
+    })
+    .await?;
+    let options = ExecuteOptions::new().loops(3);
+    let result = conn
+        .execute_with("playback", "tone_stream://%(100,100,350)", &options)
+        .await?;
+    assert_eq!(Some("iteration 3"), result.response());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn hangup_during_execute() -> Result<()> {
+    let conn = mock_outbound_call(|_, _| vec![channel_hangup("NORMAL_CLEARING")]).await?;
+    let result = conn.playback("ivr/ivr-welcome.wav").await;
+    assert_eq!(
+        Err(EslError::ChannelHungUp(HangupCause::NormalClearing)),
+        result
+    );
+    assert_eq!(HangupCause::NormalClearing, conn.on_hangup().await?);
+    assert_eq!(Some(HangupCause::NormalClearing), conn.hangup_cause());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_hangup() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| match command["execute-app-name"].as_ref() {
+        // CHANNEL_HANGUP is fired before hangup application completes
+        "hangup" => vec![
+            channel_hangup(&command["execute-app-arg"]),
+            execute_complete(command, serde_json::json!({"Channel-State": "CS_HANGUP"})),
+        ],
+        _ => vec![execute_complete(
+            command,
+            serde_json::json!({
+                "variable_read_result": "success",
+                "variable_ivr_menu_digits": "9",
+            }),
+        )],
+    })
+    .await?;
+    let menu = IvrMenu::new("main", "ivr/ivr-welcome.wav")
+        .action("9", IvrAction::Hangup(HangupCause::UserBusy));
+    let outcome = conn.run_ivr(&menu).await?;
+    assert_eq!(IvrOutcome::HungUp(HangupCause::UserBusy), outcome);
+    assert_eq!(Some(HangupCause::UserBusy), conn.hangup_cause());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_hangup_result() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        vec![
+            channel_hangup("NORMAL_CLEARING"),
+            execute_complete(command, serde_json::json!({"Channel-State": "CS_HANGUP"})),
+        ]
+    })
+    .await?;
+    let result = conn.hangup("NORMAL_CLEARING").await?;
+    assert!(result.hungup());
+    assert_eq!(HangupCause::NormalClearing, conn.on_hangup().await?);
+    // applications other than hangup still fail once channel is gone
+    assert_eq!(
+        Err(EslError::ChannelHungUp(HangupCause::NormalClearing)),
+        conn.playback("ivr/ivr-welcome.wav").await
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn disconnect_during_execute() -> Result<()> {
+    let conn = mock_outbound_call(|_, _| {
+        vec![
+            "Content-Type: text/disconnect-notice\nContent-Disposition: disconnect\n\n".to_string(),
+        ]
+    })
+    .await?;
+    let result = conn.playback("ivr/ivr-welcome.wav").await;
+    assert_eq!(Err(EslError::ChannelHungUp(HangupCause::None)), result);
+    assert_eq!(HangupCause::None, conn.on_hangup().await?);
+    assert!(!conn.connected());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn linger_until_hangup_complete() -> Result<()> {
+    let conn = mock_outbound_call(|command, _| {
+        if command.starts_with("linger") {
+            return vec![];
+        }
+        vec![
+            channel_hangup("NORMAL_CLEARING"),
+            "Content-Type: text/disconnect-notice\nContent-Disposition: linger\n\n".to_string(),
+            channel_hangup_complete("NORMAL_CLEARING"),
+            "Content-Type: text/disconnect-notice\nContent-Disposition: disconnect\n\n".to_string(),
+        ]
+    })
+    .await?;
+    conn.linger(Some(std::time::Duration::from_secs(10)))
+        .await?;
+    let result = conn.playback("ivr/ivr-welcome.wav").await;
+    assert_eq!(
+        Err(EslError::ChannelHungUp(HangupCause::NormalClearing)),
+        result
+    );
+    let event = conn.on_hangup_complete().await?;
+    assert!(event
+        .body()
+        .as_ref()
+        .unwrap()
+        .contains("\"variable_billsec\":\"42\""));
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn linger_option() -> Result<()> {
+    let commands = Arc::new(Mutex::new(Vec::new()));
+    let sent = Arc::clone(&commands);
+    let options = OutboundOptions::new().linger_timeout(Duration::from_millis(500));
+    let conn = mock_outbound_call_with(&options, move |command, _| {
+        sent.lock().unwrap().push(command.to_string());
+        vec![]
+    })
+    .await?;
+    conn.linger(Some(Duration::from_millis(1500))).await?;
+    conn.linger(None).await?;
+    conn.nolinger().await?;
+    assert_eq!(
+        vec!["linger 1", "linger 2", "linger", "nolinger"],
+        *commands.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_channel_data() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        vec![execute_complete(
+            command,
+            serde_json::json!({"variable_customer_id": "1234"}),
+        )]
+    })
+    .await?;
+    let channel_data = conn.channel_data().unwrap();
+    assert_eq!(CALL_UUID, channel_data.unique_id());
+    assert_eq!(Some("Karan Gauswami"), channel_data.caller_id_name());
+    assert_eq!(Some("1000"), channel_data.caller_id_number());
+    assert_eq!(Some("5000"), channel_data.destination_number());
+    assert_eq!(Some("default"), channel_data.context());
+    assert_eq!(Some(CallDirection::Inbound), channel_data.direction());
+    assert_eq!(Some("192.168.1.100"), channel_data.network_addr());
+    assert_eq!(Some("PCMU"), channel_data.read_codec());
+    assert_eq!(Some("42"), channel_data.sip_header("X-Account-ID"));
+    assert_eq!(
+        Some("sip:1000@192.168.1.100:5060"),
+        channel_data.variable("sip_contact_uri")
+    );
+    assert_eq!(None, channel_data.variable("customer_id"));
+    conn.set_variable("customer_id", "1234").await?;
+    let channel_data = conn.channel_data().unwrap();
+    assert_eq!(Some(1234), channel_data.variables().get_as("customer_id"));
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_variable_cache() -> Result<()> {
+    let conn = mock_outbound_call(|command, headers| {
+        if command.starts_with("sendmsg") {
+            return vec![execute_complete(
+                headers,
+                serde_json::json!({"variable_customer_id": "1234"}),
+            )];
+        }
+        let value = match command {
+            "api uuid_getvar 7f4de4bc-17d7-11dd-b7a0-db4edd065621 customer_tier" => "gold",
+            _ => "_undef_",
+        };
+        vec![format!(
+            "Content-Type: api/response\nContent-Length: {}\n\n{}",
+            value.len(),
+            value
+        )]
+    })
+    .await?;
+    assert_eq!(Some("en".to_string()), conn.get_variable("language").await?);
+    assert_eq!(
+        Some("gold".to_string()),
+        conn.get_variable("customer_tier").await?
+    );
+    assert_eq!(None, conn.get_variable("missing").await?);
+    assert_eq!(None, conn.get_info_string("variable_customer_id"));
+    conn.set_variable("customer_id", "1234").await?;
+    assert_eq!(
+        Some("1234".to_string()),
+        conn.get_info_string("variable_customer_id")
+    );
+    // CHANNEL_EXECUTE_COMPLETE has every channel variable so stale values are dropped
+    assert_eq!(None, conn.get_info_string("variable_language"));
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_multiset() -> Result<()> {
+    let conn =
+        mock_outbound_call(|_, command| vec![execute_complete(command, serde_json::json!({}))])
+            .await?;
+    let result = conn.multiset(&[("a", "1"), ("b", "2")]).await?;
+    assert_eq!("a=1 b=2", result.application_data());
+    let result = conn
+        .multiset(&[("greeting", "hello world"), ("b", "2")])
+        .await?;
+    assert_eq!("^^|greeting=hello world|b=2", result.application_data());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_play_and_get_digits() -> Result<()> {
+    let mut inputs = VecDeque::from(["1234", ""]);
+    let mut variables = serde_json::Map::new();
+    let conn = mock_outbound_call(move |_, command| {
+        let arg = &command["execute-app-arg"];
+        match command["execute-app-name"].as_str() {
+            "unset" => {
+                variables.remove(&format!("variable_{}", arg));
+            }
+            "play_and_get_digits" => match inputs.pop_front() {
+                Some("") | None => {
+                    variables.insert("variable_read_result".into(), "failure".into());
+                }
+                Some(digits) => {
+                    variables.insert("variable_pin".into(), digits.into());
+                    variables.insert("variable_read_result".into(), "success".into());
+                    variables.insert("variable_read_terminator_used".into(), "#".into());
+                }
+            },
+            _ => {
+                variables.insert("variable_read_result".into(), "timeout".into());
+            }
+        }
+        vec![execute_complete(
+            command,
+            serde_json::Value::Object(variables.clone()),
+        )]
+    })
+    .await?;
+    let options = PlayAndGetDigits::new("conference/conf-pin.wav")
+        .min(4)
+        .max(4)
+        .variable_name("pin")
+        .digits_regex("^\\d+$")
+        .digit_timeout(std::time::Duration::from_secs(2));
+    let digits = conn.play_and_get_digits(&options).await?;
+    assert_eq!(
+        "4 4 3 5000 # conference/conf-pin.wav silence_stream://250 pin ^\\d+$ 2000",
+        digits.execute_result().application_data()
+    );
+    assert_eq!(DigitsStatus::Success, digits.status());
+    assert_eq!(Some("1234"), digits.digits());
+    assert_eq!(Some("#"), digits.terminator());
+    // digits of the previous collection are not reported again
+    let digits = conn.play_and_get_digits(&options).await?;
+    assert_eq!(DigitsStatus::Timeout, digits.status());
+    assert_eq!(None, digits.digits());
+
+    let digits = conn.read(&ReadDigits::new("ivr/ivr-enter_ext.wav")).await?;
+    assert_eq!(
+        "1 1 ivr/ivr-enter_ext.wav read_digits 5000 #",
+        digits.execute_result().application_data()
+    );
+    assert_eq!(DigitsStatus::Timeout, digits.status());
+    assert_eq!(None, digits.digits());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_playback_interrupted() -> Result<()> {
+    let applications = Arc::new(Mutex::new(Vec::new()));
+    let executed = Arc::clone(&applications);
+    let conn = mock_outbound_call(move |command, headers| {
+        if !command.starts_with("sendmsg") {
+            let value = match executed.lock().unwrap().len() {
+                0 => "_undef_",
+                _ => "*",
+            };
+            return vec![format!(
+                "Content-Type: api/response\nContent-Length: {}\n\n{}",
+                value.len(),
+                value
+            )];
+        }
+        let command = headers;
+        executed.lock().unwrap().push(format!(
+            "{} {}",
+            command["execute-app-name"], command["execute-app-arg"]
+        ));
+        if command["execute-app-name"] != "playback" {
+            return vec![execute_complete(command, serde_json::json!({}))];
+        }
+        vec![
+            event_json(serde_json::json!({
+                "Event-Name": "PLAYBACK_STOP",
+                "Unique-ID": CALL_UUID,
+                "Playback-File-Path": command["execute-app-arg"],
+                "Playback-Status": "break",
+            })),
+            execute_complete(
+                command,
+                serde_json::json!({
+                    "variable_current_application_response": "FILE PLAYED",
+                    "variable_playback_terminator_used": "#",
+                    "variable_playback_ms": "1520",
+                }),
+            ),
+        ]
+    })
+    .await?;
+    let options = Playback::new("ivr/ivr-welcome.wav")
+        .file("ivr/ivr-please_hold.wav")
+        .terminators("#")
+        .start_offset(8000)
+        .volume(2);
+    let playback = conn.playback_with(&options).await?;
+    assert_eq!(
+        "{volume=2}file_string://ivr/ivr-welcome.wav@@8000!ivr/ivr-please_hold.wav",
+        playback.execute_result().application_data()
+    );
+    assert_eq!(PlaybackStatus::Break, playback.status());
+    assert_eq!(Some("#"), playback.terminator_used());
+    assert_eq!(Some(1520), playback.playback_ms());
+    assert!(playback.playback_stop().is_some());
+    conn.playback_async(&Playback::new("local_stream://moh").terminators("1"))
+        .await?;
+    conn.playback_with(&Playback::new("ivr/ivr-thank_you.wav"))
+        .await?;
+    // terminators only apply to the playback they were given for
+    assert_eq!(
+        vec![
+            "set playback_terminators=#",
+            "playback {volume=2}file_string://ivr/ivr-welcome.wav@@8000!ivr/ivr-please_hold.wav",
+            "unset playback_terminators",
+            "set playback_terminators=1",
+            "playback local_stream://moh",
+            "set playback_terminators=*",
+            "playback ivr/ivr-thank_you.wav",
+        ],
+        *applications.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_ivr_menu() -> Result<()> {
+    let mut inputs = VecDeque::from(["9", "2", "", "*", "1"]);
+    let applications = Arc::new(Mutex::new(Vec::new()));
+    let executed = Arc::clone(&applications);
+    let conn = mock_outbound_call(move |_, command| {
+        let app = &command["execute-app-name"];
+        executed
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", app, command["execute-app-arg"]));
+        let input = match app.as_str() {
+            "read" => inputs.pop_front(),
+            _ => None,
+        };
+        let variables = match input {
+            Some("") => serde_json::json!({"variable_read_result": "timeout"}),
+            Some(digits) => serde_json::json!({
+                "variable_read_result": "success",
+                "variable_ivr_menu_digits": digits,
+            }),
+            _ => serde_json::json!({}),
+        };
+        vec![execute_complete(command, variables)]
+    })
+    .await?;
+    let sales = IvrMenu::new("sales", "ivr/ivr-sales.wav")
+        .timeout_prompt("ivr/ivr-no_input.wav")
+        .action("*", IvrAction::Back);
+    let main = IvrMenu::new("main", "ivr/ivr-welcome.wav")
+        .invalid_prompt("ivr/ivr-that_was_an_invalid_entry.wav")
+        .action("1", IvrAction::Transfer("1000 XML default".into()))
+        .action("2", IvrAction::SubMenu(sales));
+    let outcome = conn.run_ivr(&main).await?;
+    assert_eq!(IvrOutcome::Transferred("1000 XML default".into()), outcome);
+    assert_eq!(
+        vec![
+            "unset ivr_menu_digits",
+            "read 1 1 ivr/ivr-welcome.wav ivr_menu_digits 5000 #",
+            "playback ivr/ivr-that_was_an_invalid_entry.wav",
+            "unset ivr_menu_digits",
+            "read 1 1 ivr/ivr-welcome.wav ivr_menu_digits 5000 #",
+            "unset ivr_menu_digits",
+            "read 1 1 ivr/ivr-sales.wav ivr_menu_digits 5000 #",
+            "playback ivr/ivr-no_input.wav",
+            "unset ivr_menu_digits",
+            "read 1 1 ivr/ivr-sales.wav ivr_menu_digits 5000 #",
+            "unset ivr_menu_digits",
+            "read 1 1 ivr/ivr-welcome.wav ivr_menu_digits 5000 #",
+            "transfer 1000 XML default",
+        ],
+        *applications.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_collect_digits() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        let mut responses: Vec<String> = ["1", "2", "#", "5"]
+            .into_iter()
+            .map(|digit| {
+                event_json(serde_json::json!({
+                    "Event-Name": "DTMF",
+                    "Unique-ID": CALL_UUID,
+                    "DTMF-Digit": digit,
+                    "DTMF-Duration": "2000",
+                    "DTMF-Source": "RTP",
+                }))
+            })
+            .collect();
+        responses.push(execute_complete(command, serde_json::json!({})));
+        responses
+    })
+    .await?;
+    let mut dtmf = conn.dtmf_events().await?;
+    conn.playback_async(&Playback::new("ivr/ivr-enter_pin.wav"))
+        .await?;
+    let options = CollectDigits::new(4)
+        .terminators("#")
+        .inter_digit_timeout(Duration::from_millis(200));
+    let digits = dtmf.collect(&options).await?;
+    assert_eq!(CollectStatus::Terminator, digits.status());
+    assert_eq!("12", digits.digits());
+    assert_eq!(Some('#'), digits.terminator());
+    let digits = dtmf.collect(&options).await?;
+    assert_eq!(CollectStatus::Timeout, digits.status());
+    assert_eq!("5", digits.digits());
+    let digits = conn.collect_digits(&options).await?;
+    assert_eq!(CollectStatus::Timeout, digits.status());
+    assert_eq!("", digits.digits());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_record() -> Result<()> {
+    let applications = Arc::new(Mutex::new(Vec::new()));
+    let executed = Arc::clone(&applications);
+    let conn = mock_outbound_call(move |command, headers| {
+        if !command.starts_with("sendmsg") {
+            return vec!["Content-Type: api/response\nContent-Length: 1\n\n*".to_string()];
+        }
+        let command = headers;
+        let app = &command["execute-app-name"];
+        let app_arg = &command["execute-app-arg"];
+        executed
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", app, app_arg));
+        if app != "record" {
+            return vec![execute_complete(command, serde_json::json!({}))];
+        }
+        vec![
+            event_json(serde_json::json!({
+                "Event-Name": "RECORD_START",
+                "Unique-ID": CALL_UUID,
+                "Record-File-Path": "/tmp/voicemail.wav",
+            })),
+            event_json(serde_json::json!({
+                "Event-Name": "RECORD_STOP",
+                "Unique-ID": CALL_UUID,
+                "Record-File-Path": "/tmp/voicemail.wav",
+                "Record-Completion-Cause": "success-silence",
+                "variable_record_ms": "4250",
+                "variable_record_seconds": "4",
+            })),
+            execute_complete(command, serde_json::json!({"variable_record_ms": "4250"})),
+        ]
+    })
+    .await?;
+    let options = Record::new("/tmp/voicemail.wav")
+        .time_limit(Duration::from_secs(60))
+        .silence_threshold(200)
+        .silence_hits(3)
+        .stereo(true)
+        .sample_rate(16000)
+        .terminators("#");
+    let record = conn.record(&options).await?;
+    assert_eq!("/tmp/voicemail.wav", record.path());
+    assert_eq!(Some(4250), record.record_ms());
+    assert_eq!(Some(Duration::from_millis(4250)), record.duration());
+    let record_stop = record.record_stop().expect("RECORD_STOP was not received");
+    assert_eq!(RecordEventKind::Stop, record_stop.kind());
+    assert_eq!(Some("success-silence"), record_stop.completion_cause());
+    assert_eq!(Some(CALL_UUID), record_stop.unique_id());
+
+    conn.record(&Record::new("/tmp/short.wav").silence_hits(3))
+        .await?;
+    conn.record(&Record::new("/tmp/short.wav").time_limit(Duration::from_millis(500)))
+        .await?;
+    let options = RecordSession::new("/tmp/call.wav").time_limit(Duration::from_secs(3600));
+    conn.record_session_with(&options).await?;
+    let options = RecordSession::new("/tmp/call.wav").time_limit(Duration::from_millis(1500));
+    conn.record_session_with(&options).await?;
+    conn.stop_record_session("all").await?;
+    assert_eq!(
+        vec![
+            "multiset RECORD_STEREO=true record_sample_rate=16000",
+            "set playback_terminators=#",
+            "record /tmp/voicemail.wav 60 200 3",
+            "set playback_terminators=*",
+            "record /tmp/short.wav 0 0 3",
+            "record /tmp/short.wav 1",
+            "record_session /tmp/call.wav +3600",
+            "record_session /tmp/call.wav +2",
+            "stop_record_session all",
+        ],
+        *applications.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_say_and_speak() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        let variables = match command["execute-app-name"].as_str() {
+            "multiset" => serde_json::json!({
+                "variable_tts_engine": "flite",
+                "variable_tts_voice": "kal",
+            }),
+            _ => serde_json::json!({}),
+        };
+        vec![execute_complete(command, variables)]
+    })
+    .await?;
+    let result = conn
+        .say(
+            &Say::new(SayType::Number, "21")
+                .language(SayLanguage::Es)
+                .gender(SayGender::Feminine),
+        )
+        .await?;
+    assert_eq!(
+        "es NUMBER PRONOUNCED FEMININE 21",
+        result.application_data()
+    );
+    let result = conn
+        .say(&Say::new(SayType::CurrentDate, "1696118400").method(SayMethod::NotApplicable))
+        .await?;
+    assert_eq!("en CURRENT_DATE N/A 1696118400", result.application_data());
+    let result = conn
+        .speak(&Speak::new("Welcome to the conference").engine("flite", "slt"))
+        .await?;
+    assert_eq!(
+        "flite|slt|Welcome to the conference",
+        result.application_data()
+    );
+    conn.set_tts("flite", "kal").await?;
+    assert_eq!(
+        Some(("flite".to_string(), "kal".to_string())),
+        conn.tts().await?
+    );
+    let result = conn.speak(&Speak::new("Goodbye")).await?;
+    assert_eq!("Goodbye", result.application_data());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_bridge() -> Result<()> {
+    let applications = Arc::new(Mutex::new(Vec::new()));
+    let executed = Arc::clone(&applications);
+    let conn = mock_outbound_call(move |command, headers| {
+        if !command.starts_with("sendmsg") {
+            // ringback was set by dialplan before bridging
+            let value = if command.ends_with(" ringback") {
+                "%(1000,4000,425)"
+            } else {
+                "_undef_"
+            };
+            return vec![api_response(value)];
+        }
+        let command = headers;
+        let app = &command["execute-app-name"];
+        let app_arg = &command["execute-app-arg"];
+        executed
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", app, app_arg));
+        let variables = match (app.as_str(), app_arg.as_str()) {
+            ("bridge", "user/1000") => serde_json::json!({
+                "Event-Date-Timestamp": "1696118442500000",
+                "variable_originate_disposition": "SUCCESS",
+                "variable_last_bridge_hangup_cause": "NORMAL_CLEARING",
+                "variable_last_bridge_to": "b1c2d3e4-0000-4000-8000-000000000001",
+                "variable_bridge_uepoch": "1696118400000000",
+            }),
+            ("bridge", _) => serde_json::json!({
+                "variable_originate_disposition": "USER_BUSY",
+            }),
+            _ => serde_json::json!({}),
+        };
+        vec![execute_complete(command, variables)]
+    })
+    .await?;
+    let options = Bridge::new("user/1000")
+        .ringback("%(2000,4000,440,480)")
+        .call_timeout(Duration::from_millis(29500))
+        .hangup_after_bridge(false);
+    let bridge = conn.bridge(&options).await?;
+    assert!(bridge.bridged());
+    assert_eq!(Some("SUCCESS"), bridge.originate_disposition());
+    assert_eq!(None, bridge.failure_cause());
+    assert_eq!(
+        Some(&HangupCause::NormalClearing),
+        bridge.last_bridge_hangup_cause()
+    );
+    assert_eq!(
+        Some("b1c2d3e4-0000-4000-8000-000000000001"),
+        bridge.b_leg_uuid()
+    );
+    assert_eq!(Some(Duration::from_millis(42500)), bridge.duration());
+
+    let options = Bridge::new("user/1001")
+        .continue_on_fail_causes(&[HangupCause::UserBusy, HangupCause::NoAnswer]);
+    let bridge = conn.bridge(&options).await?;
+    assert!(!bridge.bridged());
+    assert_eq!(Some(HangupCause::UserBusy), bridge.failure_cause());
+    assert_eq!(None, bridge.b_leg_uuid());
+    assert_eq!(
+        vec![
+            "multiset ringback=%(2000,4000,440,480) hangup_after_bridge=false call_timeout=30",
+            "bridge user/1000",
+            "unset hangup_after_bridge",
+            "unset call_timeout",
+            "multiset ringback=%(1000,4000,425)",
+            "multiset continue_on_fail=USER_BUSY,NO_ANSWER",
+            "bridge user/1001",
+            "unset continue_on_fail",
+        ],
+        *applications.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_bridge_hangup() -> Result<()> {
+    let applications = Arc::new(Mutex::new(Vec::new()));
+    let executed = Arc::clone(&applications);
+    let conn = mock_outbound_call(move |command, headers| {
+        if !command.starts_with("sendmsg") {
+            return vec![api_response("_undef_")];
+        }
+        executed
+            .lock()
+            .unwrap()
+            .push(headers["execute-app-name"].clone());
+        if headers["execute-app-name"] != "bridge" {
+            return vec![execute_complete(headers, serde_json::json!({}))];
+        }
+        // caller hangs up while bridged, bridge completes after CHANNEL_HANGUP
+        vec![
+            channel_hangup("NORMAL_CLEARING"),
+            execute_complete(
+                headers,
+                serde_json::json!({
+                    "Answer-State": "hangup",
+                    "variable_originate_disposition": "SUCCESS",
+                    "variable_last_bridge_to": "b1c2d3e4-0000-4000-8000-000000000001",
+                }),
+            ),
+        ]
+    })
+    .await?;
+    let options = Bridge::new("user/1000").hangup_after_bridge(true);
+    let bridge = conn.bridge(&options).await?;
+    assert!(bridge.bridged());
+    assert_eq!(
+        Some("b1c2d3e4-0000-4000-8000-000000000001"),
+        bridge.b_leg_uuid()
+    );
+    assert_eq!(Some(HangupCause::NormalClearing), conn.hangup_cause());
+    // variables aren't restored on a hung up call
+    assert_eq!(vec!["multiset", "bridge"], *applications.lock().unwrap());
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_transfer() -> Result<()> {
+    let conn = mock_outbound_call(|_, command| {
+        let variables = match command["execute-app-arg"].as_str() {
+            "user/1001" => serde_json::json!({"variable_originate_disposition": "USER_BUSY"}),
+            "user/1002" => serde_json::json!({
+                "variable_originate_disposition": "SUCCESS",
+                "variable_last_bridge_to": "c1c2d3e4-0000-4000-8000-000000000002",
+            }),
+            // transferor hangs up once bridged to target
+            "user/1003" => {
+                return vec![
+                    event_json(serde_json::json!({
+                        "Event-Name": "CHANNEL_BRIDGE",
+                        "Unique-ID": CALL_UUID,
+                        "Other-Leg-Unique-ID": "d1c2d3e4-0000-4000-8000-000000000003",
+                    })),
+                    channel_hangup("NORMAL_CLEARING"),
+                ]
+            }
+            // transferor hangs up while target is still ringing
+            "user/1004" => return vec![channel_hangup("ORIGINATOR_CANCEL")],
+            _ => serde_json::json!({}),
+        };
+        vec![execute_complete(command, variables)]
+    })
+    .await?;
+    let result = conn
+        .transfer(&Transfer::new("1000").context("default"))
+        .await?;
+    assert_eq!("1000 XML default", result.application_data());
+    let result = conn.deflect("sip:1000@example.com").await?;
+    assert_eq!("sip:1000@example.com", result.application_data());
+
+    let att_xfer = conn.att_xfer("user/1001").await?;
+    assert_eq!(
+        &TransferOutcome::Failed(HangupCause::UserBusy),
+        att_xfer.outcome()
+    );
+    let att_xfer = conn.att_xfer("user/1002").await?;
+    assert_eq!(&TransferOutcome::Cancelled, att_xfer.outcome());
+    assert_eq!(
+        Some("c1c2d3e4-0000-4000-8000-000000000002"),
+        att_xfer.target_uuid()
+    );
+    let att_xfer = conn.att_xfer("user/1003").await?;
+    assert_eq!(&TransferOutcome::Completed, att_xfer.outcome());
+    assert_eq!(
+        Some("d1c2d3e4-0000-4000-8000-000000000003"),
+        att_xfer.target_uuid()
+    );
+    assert!(att_xfer.execute_result().is_none());
+    let att_xfer = conn.att_xfer("user/1004").await?;
+    assert_eq!(
+        &TransferOutcome::Abandoned(HangupCause::OriginatorCancel),
+        att_xfer.outcome()
+    );
+    assert_eq!(None, att_xfer.target_uuid());
+    Ok(())
+}
+
+fn channel_bridge(unique_id: &str, other_leg: &str) -> String {
+    event_json(serde_json::json!({
+        "Event-Name": "CHANNEL_BRIDGE",
+        "Unique-ID": unique_id,
+        "Other-Leg-Unique-ID": other_leg,
+    }))
+}
+
+fn leg_hangup(unique_id: &str, hangup_cause: &str) -> String {
+    event_json(serde_json::json!({
+        "Event-Name": "CHANNEL_HANGUP",
+        "Unique-ID": unique_id,
+        "Hangup-Cause": hangup_cause,
+    }))
+}
+
+fn api_response(body: &str) -> String {
+    format!(
+        "Content-Type: api/response\nContent-Length: {}\n\n{}",
+        body.len(),
+        body
+    )
+}
+
+fn background_job(command: &HashMap<String, String>, body: &str) -> Vec<String> {
+    let job_uuid = &command["Job-UUID"];
+    vec![
+        format!(
+            "Content-Type: command/reply\nReply-Text: +OK Job-UUID: {}\nJob-UUID: {}\n\n",
+            job_uuid, job_uuid
+        ),
+        event_json(serde_json::json!({
+            "Event-Name": "BACKGROUND_JOB",
+            "Job-UUID": job_uuid,
+            "Job-Command": "originate",
+            "_body": body,
+        })),
+    ]
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn outbound_consult_transfer() -> Result<()> {
+    let commands = Arc::new(Mutex::new(Vec::new()));
+    let sent = Arc::clone(&commands);
+    let conn = mock_outbound_call(move |command, headers| {
+        let command = command.lines().next().unwrap_or_default().to_string();
+        sent.lock().unwrap().push(command.clone());
+        match command.as_str() {
+            "bgapi originate {origination_uuid=target}user/1001 &park()" => {
+                background_job(headers, "+OK target\n")
+            }
+            "bgapi originate {origination_uuid=busy,originate_timeout=20}user/1002 &park()" => {
+                background_job(headers, "-ERR USER_BUSY\n")
+            }
+            "bgapi originate {origination_uuid=gone}user/1003 &park()" => {
+                background_job(headers, "+OK gone\n")
+            }
+            "api uuid_bridge customer gone" | "api uuid_bridge missing target" => {
+                vec![api_response("-ERR No such channel!\n")]
+            }
+            "api uuid_exists gone" => vec![api_response("false")],
+            "api uuid_exists target" => vec![api_response("true")],
+            _ => vec![api_response("+OK\n")],
+        }
+    })
+    .await?;
+    let options = ConsultTransfer::new("customer", "agent", "user/1001")
+        .target_uuid("target")
+        .hold_music("local_stream://moh");
+    let consultation = conn.consult(&options).await?;
+    assert_eq!("target", consultation.target_uuid());
+    assert_eq!(
+        TransferOutcome::Completed,
+        conn.complete_transfer(&consultation).await?
+    );
+    let consultation = conn
+        .consult(&ConsultTransfer::new("customer", "agent", "user/1001").target_uuid("target"))
+        .await?;
+    assert_eq!(
+        TransferOutcome::Cancelled,
+        conn.cancel_transfer(&consultation).await?
+    );
+    let options = ConsultTransfer::new("customer", "agent", "user/1002")
+        .target_uuid("busy")
+        .timeout(Duration::from_secs(20));
+    assert_eq!(
+        Err(EslError::OriginateFailed(HangupCause::UserBusy)),
+        conn.consult(&options).await
+    );
+    // target hung up during consultation, customer goes back to agent
+    let consultation = conn
+        .consult(&ConsultTransfer::new("customer", "agent", "user/1003").target_uuid("gone"))
+        .await?;
+    assert_eq!(
+        TransferOutcome::Failed(HangupCause::NormalClearing),
+        conn.complete_transfer(&consultation).await?
+    );
+    // customer hung up during consultation
+    let consultation = conn
+        .consult(&ConsultTransfer::new("missing", "agent", "user/1001").target_uuid("target"))
+        .await?;
+    assert_eq!(
+        Err(EslError::NoSuchChannel("missing".into())),
+        conn.complete_transfer(&consultation).await
+    );
+    assert_eq!(
+        vec![
+            "bgapi originate {origination_uuid=target}user/1001 &park()",
+            "api uuid_setvar customer park_after_bridge true",
+            "api uuid_setvar agent park_after_bridge true",
+            "api uuid_bridge agent target",
+            "api uuid_broadcast customer local_stream://moh aleg",
+            "api uuid_break customer all",
+            "api uuid_bridge customer target",
+            "bgapi originate {origination_uuid=target}user/1001 &park()",
+            "api uuid_setvar customer park_after_bridge true",
+            "api uuid_setvar agent park_after_bridge true",
+            "api uuid_bridge agent target",
+            "api uuid_kill target NORMAL_CLEARING",
+            "api uuid_bridge agent customer",
+            "bgapi originate {origination_uuid=busy,originate_timeout=20}user/1002 &park()",
+            "bgapi originate {origination_uuid=gone}user/1003 &park()",
+            "api uuid_setvar customer park_after_bridge true",
+            "api uuid_setvar agent park_after_bridge true",
+            "api uuid_bridge agent gone",
+            "api uuid_bridge customer gone",
+            "api uuid_exists gone",
+            "api uuid_bridge agent customer",
+            "bgapi originate {origination_uuid=target}user/1001 &park()",
+            "api uuid_setvar missing park_after_bridge true",
+            "api uuid_setvar agent park_after_bridge true",
+            "api uuid_bridge agent target",
+            "api uuid_bridge missing target",
+            "api uuid_exists target",
+        ],
+        *commands.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[timeout(10000)]
+async fn transfer_events() -> Result<()> {
+    let conn = mock_outbound_call(|command, headers| {
+        let command = command.lines().next().unwrap_or_default();
+        if command.starts_with("bgapi originate") {
+            return background_job(headers, "+OK\n");
+        }
+        let mut responses = vec![api_response("+OK\n")];
+        match command {
+            "api uuid_bridge customer target" => responses.extend([
+                // agent hanging up and other calls don't end the transfer
+                channel_bridge("other", "target"),
+                leg_hangup("agent", "NORMAL_CLEARING"),
+                channel_bridge("target", "customer"),
+            ]),
+            "api uuid_bridge agent customer" => {
+                responses.push(channel_bridge("agent", "customer"));
+            }
+            "api uuid_kill caller ORIGINATOR_CANCEL" => {
+                responses.push(leg_hangup("caller", "ORIGINATOR_CANCEL"));
+            }
+            _ => {}
+        }
+        responses
+    })
+    .await?;
+    let options = ConsultTransfer::new("customer", "agent", "user/1001").target_uuid("target");
+    let consultation = conn.consult(&options).await?;
+    let mut events = conn.transfer_events(&consultation).await?;
+    conn.complete_transfer(&consultation).await?;
+    assert_eq!(Some(TransferOutcome::Completed), events.recv().await);
+
+    let consultation = conn.consult(&options).await?;
+    let mut events = conn.transfer_events(&consultation).await?;
+    conn.cancel_transfer(&consultation).await?;
+    assert_eq!(Some(TransferOutcome::Cancelled), events.recv().await);
+
+    // customer hangs up while waiting for consultation to end
+    let options = ConsultTransfer::new("caller", "agent", "user/1001").target_uuid("target");
+    let consultation = conn.consult(&options).await?;
+    let mut events = conn.transfer_events(&consultation).await?;
+    conn.uuid_kill("caller", &HangupCause::OriginatorCancel)
+        .await?;
+    assert_eq!(
+        Some(TransferOutcome::Abandoned(HangupCause::OriginatorCancel)),
+        events.recv().await
+    );
+    Ok(())
+}